@@ -0,0 +1,351 @@
+//! Central 网络与成员的备份与恢复
+//!
+//! 备份为带版本号的 JSON 归档，包含完整的 `Network`（配置与规则源）及其全部成员。
+
+use crate::central::{self, Central, Member, Network, UpdateMemberRequest, UpdateNetworkRequest};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 当前归档格式版本
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// 备份归档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Archive {
+    pub version: u32,
+    /// 导出时间（毫秒时间戳）
+    pub created_at: i64,
+    pub networks: Vec<NetworkBackup>,
+}
+
+/// 单个网络的备份
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkBackup {
+    pub network: Network,
+    pub members: Vec<Member>,
+}
+
+/// 恢复选项
+#[derive(Debug, Clone)]
+pub struct RestoreOptions {
+    /// 网络不存在时新建（新网络 ID 由 Central 分配）；ID 不存在但有唯一同名网络时视为已存在
+    pub create_missing: bool,
+    /// 网络已存在时原地更新
+    pub update_existing: bool,
+    /// 仅恢复指定网络 ID，为空时恢复全部
+    pub networks: Vec<String>,
+}
+
+impl Default for RestoreOptions {
+    fn default() -> Self {
+        Self {
+            create_missing: true,
+            update_existing: true,
+            networks: Vec::new(),
+        }
+    }
+}
+
+/// 恢复结果，单个网络或成员的失败记录在结果中，不影响其余网络
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoreReport {
+    pub networks: Vec<RestoredNetwork>,
+}
+
+impl RestoreReport {
+    /// 所有网络与成员都已恢复（或按选项跳过）
+    pub fn is_complete(&self) -> bool {
+        self.networks.iter().all(|n| n.error.is_none() && n.failed_members.is_empty())
+    }
+}
+
+/// 单个网络的恢复结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoredNetwork {
+    /// 归档中的网络 ID
+    pub source_id: String,
+    /// 恢复后的网络 ID（新建或按名称匹配时与 `source_id` 不同）
+    pub network_id: String,
+    pub name: String,
+    pub created: bool,
+    /// 因选项被跳过
+    pub skipped: bool,
+    /// 已恢复的成员数
+    pub members: usize,
+    /// 网络未能恢复的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 恢复失败的成员
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed_members: Vec<FailedMember>,
+}
+
+/// 恢复失败的成员
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedMember {
+    pub member_id: String,
+    pub error: String,
+}
+
+/// 归档中的网络在账户中对应的恢复目标
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    /// ID 相同或唯一同名的现有网络
+    Existing(String),
+    Missing,
+    /// ID 不存在且有多个同名网络
+    Ambiguous(Vec<String>),
+}
+
+fn network_name(network: &Network) -> &str {
+    network.config.as_ref().map_or("", |c| c.name.as_str())
+}
+
+/// 先按 ID、再按名称查找恢复目标；按名称匹配使新建后中断的恢复可以重复执行而不产生重复网络
+fn find_target(source: &Network, existing: &[Network]) -> Target {
+    if let Some(network) = existing.iter().find(|n| n.id.eq_ignore_ascii_case(&source.id)) {
+        return Target::Existing(network.id.clone());
+    }
+    let name = network_name(source);
+    if name.is_empty() {
+        return Target::Missing;
+    }
+    let mut same: Vec<String> = existing.iter().filter(|n| network_name(n) == name).map(|n| n.id.clone()).collect();
+    match same.len() {
+        0 => Target::Missing,
+        1 => Target::Existing(same.remove(0)),
+        _ => Target::Ambiguous(same),
+    }
+}
+
+impl Archive {
+    /// 导出账户下所有网络及其成员
+    pub async fn export(central: &Central) -> Result<Self, Error> {
+        let service = central.networks();
+        let mut networks = Vec::new();
+
+        for network in service.list().await? {
+            // 列表接口可能省略部分字段，逐个读取完整网络
+            let network = service.get(&network.id).await?;
+            let members = service.members(&network.id).list().await?;
+            networks.push(NetworkBackup { network, members });
+        }
+
+        Ok(Self {
+            version: ARCHIVE_VERSION,
+            created_at: now_millis(),
+            networks,
+        })
+    }
+
+    /// 从 JSON 文本解析
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let archive: Self = serde_json::from_str(s)?;
+        if archive.version > ARCHIVE_VERSION {
+            return Err(Error::UnsupportedVersion(archive.version));
+        }
+        Ok(archive)
+    }
+
+    /// 读取归档文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// 写入归档文件
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 恢复网络与成员；只有列出现有网络失败时返回错误，其余失败记录在报告中
+    pub async fn restore(&self, central: &Central, opts: &RestoreOptions) -> Result<RestoreReport, Error> {
+        let service = central.networks();
+        let existing = service.list().await?;
+        let mut report = RestoreReport::default();
+
+        for backup in &self.networks {
+            let source = &backup.network;
+            if !opts.networks.is_empty() && !opts.networks.iter().any(|id| id.eq_ignore_ascii_case(&source.id)) {
+                continue;
+            }
+
+            let target = find_target(source, &existing);
+            let mut result = RestoredNetwork {
+                source_id: source.id.clone(),
+                network_id: source.id.clone(),
+                name: network_name(source).to_string(),
+                created: target == Target::Missing,
+                skipped: false,
+                members: 0,
+                error: None,
+                failed_members: Vec::new(),
+            };
+
+            let req = UpdateNetworkRequest {
+                config: source.config.as_ref().map(Into::into),
+                description: Some(source.description.clone()),
+                rules_source: Some(source.rules_source.clone()),
+            };
+            let network = match target {
+                Target::Existing(_) if !opts.update_existing => None,
+                Target::Missing if !opts.create_missing => None,
+                Target::Existing(id) => Some(service.update_with(&id, &req).await),
+                Target::Missing => Some(service.create_with(&req).await),
+                Target::Ambiguous(ids) => {
+                    result.error = Some(format!("Multiple networks named \"{}\": {}", result.name, ids.join(", ")));
+                    report.networks.push(result);
+                    continue;
+                }
+            };
+            let network = match network {
+                None => {
+                    result.skipped = true;
+                    report.networks.push(result);
+                    continue;
+                }
+                Some(Ok(network)) => network,
+                Some(Err(e)) => {
+                    result.error = Some(e.to_string());
+                    report.networks.push(result);
+                    continue;
+                }
+            };
+            result.network_id = network.id.clone();
+
+            let members = service.members(&network.id);
+            for member in &backup.members {
+                match members.update(&member.node_id, &UpdateMemberRequest::from(member)).await {
+                    Ok(_) => result.members += 1,
+                    Err(e) => result.failed_members.push(FailedMember { member_id: member.node_id.clone(), error: e.to_string() }),
+                }
+            }
+
+            report.networks.push(result);
+        }
+
+        Ok(report)
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// 错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Central error: {0}")]
+    Central(#[from] central::Error),
+
+    #[error("Unsupported archive version: {0} (max {ARCHIVE_VERSION})")]
+    UnsupportedVersion(u32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn network(id: &str, name: &str) -> Network {
+        serde_json::from_value(json!({
+            "id": id,
+            "clock": 0,
+            "config": {
+                "id": id,
+                "name": name,
+                "private": true,
+                "creationTime": 0,
+                "lastModified": 0,
+                "enableBroadcast": true,
+                "mtu": 2800,
+                "multicastLimit": 32,
+            },
+            "rulesSource": "accept;",
+            "onlineMemberCount": 0,
+            "authorizedMemberCount": 0,
+            "totalMemberCount": 0,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn archive_round_trips_through_json() {
+        let archive = Archive {
+            version: ARCHIVE_VERSION,
+            created_at: 1_700_000_000_000,
+            networks: vec![NetworkBackup { network: network("8056c2e21c000001", "homelab"), members: Vec::new() }],
+        };
+        let parsed = Archive::from_json(&serde_json::to_string(&archive).unwrap()).unwrap();
+        assert_eq!(parsed.version, ARCHIVE_VERSION);
+        assert_eq!(parsed.created_at, archive.created_at);
+        assert_eq!(parsed.networks.len(), 1);
+        assert_eq!(parsed.networks[0].network.id, "8056c2e21c000001");
+        assert_eq!(network_name(&parsed.networks[0].network), "homelab");
+        assert_eq!(parsed.networks[0].network.rules_source, "accept;");
+    }
+
+    #[test]
+    fn newer_archive_versions_are_rejected() {
+        let text = json!({ "version": ARCHIVE_VERSION + 1, "createdAt": 0, "networks": [] }).to_string();
+        assert!(matches!(Archive::from_json(&text), Err(Error::UnsupportedVersion(v)) if v == ARCHIVE_VERSION + 1));
+    }
+
+    #[test]
+    fn target_is_matched_by_id_first() {
+        let existing = [network("8056c2e21c000001", "other"), network("8056c2e21c000002", "homelab")];
+        let source = network("8056C2E21C000001", "homelab");
+        assert_eq!(find_target(&source, &existing), Target::Existing("8056c2e21c000001".into()));
+    }
+
+    #[test]
+    fn missing_network_is_matched_by_unique_name() {
+        // 上次恢复新建后中断：ID 已变化，名称相同
+        let existing = [network("8056c2e21c0000aa", "homelab")];
+        let source = network("8056c2e21c000001", "homelab");
+        assert_eq!(find_target(&source, &existing), Target::Existing("8056c2e21c0000aa".into()));
+        assert_eq!(find_target(&network("8056c2e21c000002", "lab"), &existing), Target::Missing);
+    }
+
+    #[test]
+    fn unnamed_or_duplicate_names_are_not_guessed() {
+        let existing = [network("8056c2e21c0000aa", ""), network("8056c2e21c0000bb", "lab"), network("8056c2e21c0000cc", "lab")];
+        assert_eq!(find_target(&network("8056c2e21c000001", ""), &existing), Target::Missing);
+        assert_eq!(
+            find_target(&network("8056c2e21c000002", "lab"), &existing),
+            Target::Ambiguous(vec!["8056c2e21c0000bb".into(), "8056c2e21c0000cc".into()])
+        );
+    }
+
+    #[test]
+    fn report_is_complete_only_without_failures() {
+        let restored = RestoredNetwork {
+            source_id: "1".into(),
+            network_id: "1".into(),
+            name: String::new(),
+            created: false,
+            skipped: true,
+            members: 0,
+            error: None,
+            failed_members: Vec::new(),
+        };
+        let mut report = RestoreReport { networks: vec![restored.clone()] };
+        assert!(report.is_complete());
+        report.networks.push(RestoredNetwork {
+            failed_members: vec![FailedMember { member_id: "abcdef0123".into(), error: "API error (500)".into() }],
+            ..restored
+        });
+        assert!(!report.is_complete());
+    }
+}
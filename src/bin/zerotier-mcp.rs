@@ -1,26 +1,243 @@
-use zerotier_sdk_rust_mcp::{Client, McpServer};
+use zerotier_sdk_rust_mcp::{
+    backup::{Archive, RestoreOptions},
+    controller::{migrate_network, Error as ControllerError, MigrateOptions, MigrationReport},
+    mcp::{AccessPolicy, AuditLog, HttpOptions, Locale, NetworkPolicy, ProfilesConfig},
+    state::DesiredState,
+    Central, Client, McpServer, NetworkController,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+const USAGE: &str = "用法:
+  zerotier-mcp                 启动 MCP stdio 服务
+                               环境变量: ZEROTIER_MCP_READ_ONLY、ZEROTIER_MCP_ALLOW_TOOLS、
+                               ZEROTIER_MCP_DENY_TOOLS（工具访问控制）、ZEROTIER_MCP_POLICY（按网络的操作策略文件）、
+                               ZEROTIER_MCP_CONFIRM（破坏性操作二次确认）、ZEROTIER_MCP_AUDIT_LOG（审计日志路径）、
+                               ZEROTIER_MCP_PROFILES（多账号/多节点 profile 配置文件）、
+                               ZEROTIER_MCP_LANG（输出语言 zh / en）、ZEROTIER_MCP_DRY_RUN（演练模式，写操作只返回预期变更）
+  zerotier-mcp http [addr]     启动 MCP HTTP 服务（Streamable HTTP: /mcp，SSE: /sse），默认 127.0.0.1:8080
+                               环境变量: ZEROTIER_MCP_HTTP_TOKEN（Bearer Token）、
+                               ZEROTIER_MCP_CORS_ORIGINS（逗号分隔的来源，* 为任意）
+  zerotier-mcp plan <file>     对比期望状态文件与 Central，输出变更计划
+  zerotier-mcp apply <file>    执行期望状态文件中的变更
+  zerotier-mcp export <file>   备份所有 Central 网络及成员到 JSON 归档
+  zerotier-mcp restore <file> [network_id...]
+                               从归档恢复网络（不存在则新建，存在则原地更新）
+  zerotier-mcp migrate <to-local|to-central> <network_id> [target_network_id]
+                               在 Central 与本机自托管控制器之间迁移网络";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 从环境变量获取本地 API Token（可选，默认自动读取系统 authtoken.secret）
-    let local_token = std::env::var("ZEROTIER_LOCAL_TOKEN").ok();
-    
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        None => server_from_env()?.serve_stdio().await,
+        Some("http") => serve_http(args.get(1).map(String::as_str)).await,
+        Some(cmd @ ("plan" | "apply")) => {
+            let path = args.get(1).ok_or(USAGE)?;
+            run_state(cmd == "apply", path).await
+        }
+        Some("export") => {
+            let path = args.get(1).ok_or(USAGE)?;
+            let archive = Archive::export(&central_from_env()?).await?;
+            archive.save(path)?;
+            println!("已导出 {} 个网络到 {}", archive.networks.len(), path);
+            Ok(())
+        }
+        Some("restore") => {
+            let path = args.get(1).ok_or(USAGE)?;
+            let opts = RestoreOptions {
+                networks: args[2..].to_vec(),
+                ..Default::default()
+            };
+            let report = Archive::load(path)?.restore(&central_from_env()?, &opts).await?;
+            for n in &report.networks {
+                if let Some(error) = &n.error {
+                    println!("失败 \"{}\": {}: {}", n.name, n.source_id, error);
+                    continue;
+                }
+                let action = if n.skipped { "跳过" } else if n.created { "新建" } else { "更新" };
+                println!("{} \"{}\": {} -> {}（{} 个成员）", action, n.name, n.source_id, n.network_id, n.members);
+                for m in &n.failed_members {
+                    println!("  成员 {} 失败: {}", m.member_id, m.error);
+                }
+            }
+            if !report.is_complete() {
+                return Err("部分网络或成员未能恢复，可重新执行 restore（已新建的网络按名称匹配，不会重复创建）".into());
+            }
+            Ok(())
+        }
+        Some("migrate") => {
+            let (direction, network_id) = args.get(1).zip(args.get(2)).ok_or(USAGE)?;
+            run_migrate(direction, network_id, args.get(3).cloned()).await
+        }
+        Some(_) => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn server_from_env() -> Result<McpServer, Box<dyn std::error::Error>> {
     // 从环境变量获取 Central API Token（可选）
     let central_token = std::env::var("ZEROTIER_CENTRAL_TOKEN").ok();
 
-    // 配置本地客户端
-    let local_client = if let Some(token) = local_token {
-        Client::with_token(token)
-    } else {
-        Client::new()
-    };
+    let mut server = McpServer::new().with_local_client(local_from_env());
 
-    let mut server = McpServer::new().with_local_client(local_client);
-    
     if let Some(token) = central_token {
         server = server.with_central_token(token);
     }
+    if let Ok(path) = std::env::var("ZEROTIER_MCP_PROFILES") {
+        let config = ProfilesConfig::load(&path).map_err(|e| format!("读取 profile 配置 {} 失败: {}", path, e))?;
+        let profiles = config.build().map_err(|e| format!("读取 profile 配置 {} 失败: {}", path, e))?;
+        for (name, profile) in profiles {
+            server = server.with_profile(name, profile);
+        }
+        if let Some(default) = config.default {
+            server = server.with_default_profile(default);
+        }
+    }
+    if let Ok(path) = std::env::var("ZEROTIER_MCP_POLICY") {
+        let policy = NetworkPolicy::load(&path).map_err(|e| format!("读取策略文件 {} 失败: {}", path, e))?;
+        server = server.with_network_policy(policy);
+    }
+    if let Ok(path) = std::env::var("ZEROTIER_MCP_AUDIT_LOG") {
+        let mut log = AuditLog::new(path);
+        if let Some(mb) = std::env::var("ZEROTIER_MCP_AUDIT_MAX_MB").ok().and_then(|v| v.parse::<u64>().ok()) {
+            log = log.max_size(mb * 1024 * 1024);
+        }
+        if let Some(count) = std::env::var("ZEROTIER_MCP_AUDIT_MAX_FILES").ok().and_then(|v| v.parse().ok()) {
+            log = log.max_files(count);
+        }
+        server = server.with_audit_log(log);
+    }
+    // 1 / true 使用默认有效期，大于 1 的数字视为有效期秒数
+    if let Ok(value) = std::env::var("ZEROTIER_MCP_CONFIRM") {
+        match value.parse::<u64>() {
+            Ok(secs) if secs > 1 => server = server.with_confirmation_ttl(Duration::from_secs(secs)),
+            _ => server = server.with_confirmation(matches!(value.as_str(), "1" | "true" | "yes")),
+        }
+    }
+    if let Ok(lang) = std::env::var("ZEROTIER_MCP_LANG") {
+        server = server.with_locale(lang.parse::<Locale>()?);
+    }
+    if std::env::var("ZEROTIER_MCP_DRY_RUN").is_ok_and(|v| matches!(v.as_str(), "1" | "true" | "yes")) {
+        server = server.with_dry_run(true);
+    }
+    Ok(server.with_access_policy(access_from_env()))
+}
+
+/// 从环境变量读取工具访问策略
+fn access_from_env() -> AccessPolicy {
+    let read_only = std::env::var("ZEROTIER_MCP_READ_ONLY").is_ok_and(|v| matches!(v.as_str(), "1" | "true" | "yes"));
+    let mut policy = AccessPolicy::new().read_only(read_only);
+    for name in env_list("ZEROTIER_MCP_ALLOW_TOOLS") {
+        policy = policy.allow_tool(name);
+    }
+    for name in env_list("ZEROTIER_MCP_DENY_TOOLS") {
+        policy = policy.deny_tool(name);
+    }
+    policy
+}
 
-    server.serve_stdio().await?;
+/// 读取逗号分隔的环境变量
+fn env_list(key: &str) -> Vec<String> {
+    std::env::var(key)
+        .map(|v| v.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+async fn serve_http(addr: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let bind: SocketAddr = addr.unwrap_or("127.0.0.1:8080").parse()?;
+    let mut options = HttpOptions::new(bind);
+
+    match std::env::var("ZEROTIER_MCP_HTTP_TOKEN") {
+        Ok(token) if !token.is_empty() => options = options.bearer_token(token),
+        _ if !bind.ip().is_loopback() => {
+            eprintln!("警告: 监听非本机地址 {} 但未设置 ZEROTIER_MCP_HTTP_TOKEN，任何人都可以访问", bind);
+        }
+        _ => {}
+    }
+    for origin in env_list("ZEROTIER_MCP_CORS_ORIGINS") {
+        options = options.allow_origin(origin);
+    }
+
+    let server = server_from_env()?;
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    eprintln!("MCP HTTP 服务已启动: http://{}/mcp（SSE: /sse）", listener.local_addr()?);
+    server.serve_http_on(listener, options).await
+}
+
+/// 从环境变量获取本地 API Token（可选，默认自动读取系统 authtoken.secret）
+fn local_from_env() -> Client {
+    match std::env::var("ZEROTIER_LOCAL_TOKEN") {
+        Ok(token) => Client::with_token(token),
+        Err(_) => Client::new(),
+    }
+}
+
+fn central_from_env() -> Result<Central, Box<dyn std::error::Error>> {
+    let token = std::env::var("ZEROTIER_CENTRAL_TOKEN").map_err(|_| "未配置 ZEROTIER_CENTRAL_TOKEN")?;
+    Ok(Central::new(token))
+}
+
+async fn run_state(apply: bool, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let central = central_from_env()?;
+
+    let state = DesiredState::load(path)?;
+    let plan = state.plan(&central).await?;
+    print!("{}", plan);
+
+    if apply && !plan.is_empty() {
+        let report = plan.apply(&central).await?;
+        for (name, id) in &report.created_networks {
+            println!("已创建网络 \"{}\": {}", name, id);
+        }
+        println!(
+            "执行完成：新建网络 {} 个，更新 {} 个；新建成员 {} 个，更新 {} 个，取消授权 {} 个。",
+            report.created_networks.len(),
+            report.updated_networks,
+            report.created_members,
+            report.updated_members,
+            report.deauthorized_members,
+        );
+    }
     Ok(())
 }
+
+async fn run_migrate(direction: &str, network_id: &str, target_network_id: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let central = central_from_env()?;
+    let local = local_from_env().controller();
+    let (source, target): (&dyn NetworkController, &dyn NetworkController) = match direction {
+        "to-local" => (&central, &local),
+        "to-central" => (&local, &central),
+        _ => return Err(USAGE.into()),
+    };
+
+    let opts = MigrateOptions { target_network_id, ..Default::default() };
+    let report = match migrate_network(source, target, network_id, &opts).await {
+        Ok(report) => report,
+        // 目标网络已写入，先输出已完成的部分
+        Err(ControllerError::Incomplete { report, source }) => {
+            print_migration(&report);
+            return Err(source.into());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    print_migration(&report);
+    Ok(())
+}
+
+fn print_migration(report: &MigrationReport) {
+    let action = if report.created { "新建" } else { "更新" };
+    println!("{} -> {}（{}），已迁移 {} 个成员", report.source_network_id, report.target_network_id, action, report.members.len());
+    for issue in &report.issues {
+        match &issue.member_id {
+            Some(member) => println!("  ! 成员 {} {}: {}", member, issue.field, issue.message),
+            None => println!("  ! {}: {}", issue.field, issue.message),
+        }
+    }
+    if report.network_id_changed() {
+        println!("成员需要加入新网络: zerotier-cli join {}", report.target_network_id);
+    }
+}
@@ -0,0 +1,216 @@
+//! 成员批量操作：按条件筛选成员，以有限并发逐个执行，并按成员报告结果
+
+use super::{Error, Member, MemberFilter, MemberService, UpdateMemberConfig, UpdateMemberRequest};
+use crate::dry_run::PlannedWrite;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 默认并发数
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// 批量操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    Authorize,
+    Deauthorize,
+    /// 替换成员的标签（`(标签 ID, 值)`）
+    SetTags(Vec<(i32, i32)>),
+    Delete,
+    /// 按模板重命名，支持 `{name}`（原名称）、`{id}`（成员 ID）与 `{n}`（在筛选结果中的序号，从 1 开始）
+    Rename(String),
+}
+
+impl BulkAction {
+    /// 操作名
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Authorize => "authorize",
+            Self::Deauthorize => "deauthorize",
+            Self::SetTags(_) => "set_tags",
+            Self::Delete => "delete",
+            Self::Rename(_) => "rename",
+        }
+    }
+}
+
+/// 单个成员完成后的进度
+#[derive(Debug, Clone)]
+pub struct BulkProgress {
+    /// 已完成数量
+    pub completed: usize,
+    pub total: usize,
+    pub member_id: String,
+    pub ok: bool,
+}
+
+/// 单个成员的执行结果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BulkResult {
+    pub member_id: String,
+    /// 执行前的成员名称
+    pub name: String,
+    pub ok: bool,
+    /// 重命名后的名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    /// 失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 演练模式下未发送的写请求
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned: Option<PlannedWrite>,
+}
+
+impl BulkResult {
+    /// 未能得到执行结果的成员
+    fn failed(member: &Member) -> Self {
+        Self {
+            member_id: member.node_id.clone(),
+            name: member.name.clone(),
+            ok: false,
+            new_name: None,
+            error: None,
+            planned: None,
+        }
+    }
+}
+
+/// 批量操作报告，部分失败不影响其余成员
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BulkReport {
+    pub network_id: String,
+    /// 操作名
+    pub action: String,
+    /// 匹配筛选条件的成员数
+    pub matched: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// 按筛选结果顺序排列的逐个成员结果
+    pub results: Vec<BulkResult>,
+}
+
+type ProgressFn = Arc<dyn Fn(&BulkProgress) + Send + Sync>;
+
+/// 成员批量操作，通过 [`MemberService::bulk`] 创建
+pub struct BulkOperation {
+    service: MemberService,
+    action: BulkAction,
+    filter: MemberFilter,
+    concurrency: usize,
+    on_progress: Option<ProgressFn>,
+}
+
+impl BulkOperation {
+    pub(crate) fn new(service: MemberService, action: BulkAction) -> Self {
+        Self { service, action, filter: MemberFilter::default(), concurrency: DEFAULT_CONCURRENCY, on_progress: None }
+    }
+
+    /// 设置成员筛选条件（默认匹配全部成员）
+    pub fn filter(mut self, filter: MemberFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// 设置最大并发数（至少为 1，默认 4）
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// 每个成员完成后回调，回调顺序即完成顺序
+    pub fn on_progress(mut self, f: impl Fn(&BulkProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(f));
+        self
+    }
+
+    /// 列出并筛选成员后执行；只有列出成员失败时返回错误，单个成员的失败记录在报告中
+    pub async fn run(self) -> Result<BulkReport, Error> {
+        let mut members = self.filter.apply(self.service.list().await?);
+        members.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        let total = members.len();
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+        // 任务 panic 或被取消时没有返回值，按任务 ID 找回对应的成员
+        let mut spawned = HashMap::new();
+        for (index, member) in members.into_iter().enumerate() {
+            let service = self.service.fork();
+            let action = self.action.clone();
+            let semaphore = semaphore.clone();
+            let failed = BulkResult::failed(&member);
+            let handle = tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                execute(&service, &action, member, index + 1).await
+            });
+            spawned.insert(handle.id(), (index, failed));
+        }
+
+        let mut results: Vec<Option<BulkResult>> = vec![None; total];
+        let mut completed = 0;
+        while let Some(joined) = tasks.join_next_with_id().await {
+            let id = match &joined {
+                Ok((id, _)) => *id,
+                Err(e) => e.id(),
+            };
+            let Some((index, mut failed)) = spawned.remove(&id) else { continue };
+            let result = match joined {
+                Ok((_, result)) => result,
+                Err(e) => {
+                    failed.error = Some(e.to_string());
+                    failed
+                }
+            };
+            completed += 1;
+            if let Some(f) = &self.on_progress {
+                f(&BulkProgress { completed, total, member_id: result.member_id.clone(), ok: result.ok });
+            }
+            results[index] = Some(result);
+        }
+
+        let results: Vec<BulkResult> = results.into_iter().flatten().collect();
+        // 演练记录按筛选结果顺序汇总到原客户端
+        self.service.client().record_planned(results.iter().filter_map(|r| r.planned.clone()).collect());
+        let succeeded = results.iter().filter(|r| r.ok).count();
+        Ok(BulkReport {
+            network_id: self.service.network_id().to_string(),
+            action: self.action.name().to_string(),
+            matched: total,
+            succeeded,
+            failed: total - succeeded,
+            results,
+        })
+    }
+}
+
+/// 对单个成员执行操作
+async fn execute(service: &MemberService, action: &BulkAction, member: Member, n: usize) -> BulkResult {
+    let id = member.node_id.clone();
+    let config = |config: UpdateMemberConfig| UpdateMemberRequest { config: Some(config), ..Default::default() };
+    let mut new_name = None;
+    let outcome = match action {
+        BulkAction::Authorize => service.authorize(&id).await.map(drop),
+        BulkAction::Deauthorize => service.deauthorize(&id).await.map(drop),
+        BulkAction::SetTags(tags) => {
+            let req = config(UpdateMemberConfig { tags: Some(tags.clone()), ..Default::default() });
+            service.update(&id, &req).await.map(drop)
+        }
+        BulkAction::Delete => service.delete(&id).await,
+        BulkAction::Rename(template) => {
+            let name = template.replace("{name}", &member.name).replace("{id}", &id).replace("{n}", &n.to_string());
+            let req = UpdateMemberRequest { name: Some(name.clone()), ..Default::default() };
+            new_name = Some(name);
+            service.update(&id, &req).await.map(drop)
+        }
+    };
+    let planned = service.client().take_planned_writes().into_iter().next();
+    let mut result = BulkResult { member_id: id, name: member.name, ok: true, new_name, error: None, planned };
+    if let Err(e) = outcome {
+        result.ok = false;
+        result.error = Some(e.to_string());
+    }
+    result
+}
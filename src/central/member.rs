@@ -1,6 +1,7 @@
-use super::{Central, Error, Member, UpdateMemberRequest, UpdateMemberConfig};
+use super::{BulkAction, BulkOperation, Central, Error, Member, MemberQuery, MemberQueryResult, UpdateMemberRequest, UpdateMemberConfig};
 
 /// 成员管理服务
+#[derive(Clone)]
 pub struct MemberService {
     client: Central,
     network_id: String,
@@ -11,6 +12,21 @@ impl MemberService {
         Self { client, network_id }
     }
 
+    /// 使用独立演练记录的副本（未启用演练时与原服务相同），用于按成员区分预期变更
+    pub(crate) fn fork(&self) -> Self {
+        let client = self.client.clone().with_dry_run(self.client.is_dry_run());
+        Self { client, network_id: self.network_id.clone() }
+    }
+
+    pub(crate) fn client(&self) -> &Central {
+        &self.client
+    }
+
+    /// 所属网络 ID
+    pub fn network_id(&self) -> &str {
+        &self.network_id
+    }
+
     fn base_path(&self) -> String {
         format!("/network/{}/member", self.network_id)
     }
@@ -20,6 +36,11 @@ impl MemberService {
         self.client.request(reqwest::Method::GET, &self.base_path(), None::<&()>).await
     }
 
+    /// 按条件查询成员：筛选、排序与分页在本地完成
+    pub async fn find(&self, query: &MemberQuery) -> Result<MemberQueryResult, Error> {
+        Ok(query.apply(self.list().await?))
+    }
+
     /// 获取成员详情
     pub async fn get(&self, member_id: &str) -> Result<Member, Error> {
         self.client.request(reqwest::Method::GET, &format!("{}/{}", self.base_path(), member_id), None::<&()>).await
@@ -56,16 +77,34 @@ impl MemberService {
     pub async fn delete(&self, member_id: &str) -> Result<(), Error> {
         self.client.request_empty(reqwest::Method::DELETE, &format!("{}/{}", self.base_path(), member_id)).await
     }
+
+    /// 批量操作：按条件筛选成员后以有限并发执行
+    ///
+    /// ```rust,no_run
+    /// # use zerotier_sdk_rust_mcp::central::{BulkAction, Central, MemberFilter};
+    /// # use std::time::Duration;
+    /// # async fn run(central: Central) -> Result<(), zerotier_sdk_rust_mcp::central::Error> {
+    /// let report = central.networks().members("8056c2e21c000001")
+    ///     .bulk(BulkAction::Authorize)
+    ///     .filter(MemberFilter::new().authorized(false).seen_within(Duration::from_secs(3600)))
+    ///     .concurrency(8)
+    ///     .run()
+    ///     .await?;
+    /// println!("{}/{} succeeded", report.succeeded, report.matched);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bulk(&self, action: BulkAction) -> BulkOperation {
+        BulkOperation::new(self.clone(), action)
+    }
 }
 
 /// 成员配置构建器
 #[derive(Default)]
-#[allow(dead_code)]
 pub struct MemberConfigBuilder {
     config: UpdateMemberConfig,
 }
 
-#[allow(dead_code)]
 impl MemberConfigBuilder {
     pub fn new() -> Self {
         Self::default()
@@ -81,11 +120,28 @@ impl MemberConfigBuilder {
         self
     }
 
+    pub fn no_auto_assign_ips(mut self, v: bool) -> Self {
+        self.config.no_auto_assign_ips = Some(v);
+        self
+    }
+
     pub fn ip_assignments(mut self, ips: Vec<String>) -> Self {
         self.config.ip_assignments = Some(ips);
         self
     }
 
+    /// 追加一个 IP 地址
+    pub fn ip_assignment(mut self, ip: impl Into<String>) -> Self {
+        self.config.ip_assignments.get_or_insert_with(Vec::new).push(ip.into());
+        self
+    }
+
+    /// 设置标签（`(标签 ID, 值)`）
+    pub fn tags(mut self, tags: Vec<(i32, i32)>) -> Self {
+        self.config.tags = Some(tags);
+        self
+    }
+
     pub fn build(self) -> UpdateMemberConfig {
         self.config
     }
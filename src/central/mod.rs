@@ -3,12 +3,17 @@
 mod types;
 mod network;
 mod member;
+mod bulk;
+mod query;
 
 pub use types::*;
-pub use network::NetworkService;
-pub use member::MemberService;
+pub use network::{NetworkService, NetworkConfigBuilder};
+pub use member::{MemberService, MemberConfigBuilder};
+pub use bulk::{BulkAction, BulkOperation, BulkProgress, BulkReport, BulkResult};
+pub use query::{ClientVersion, MemberFilter, MemberQuery, MemberQueryResult, MemberSort};
 
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use crate::dry_run::{self, PlannedWrite, PlannedWrites};
 use std::time::Duration;
 
 /// ZeroTier Central API 客户端
@@ -17,6 +22,8 @@ pub struct Central {
     base_url: String,
     token: String,
     http_client: reqwest::Client,
+    /// 演练模式下记录的写请求，未启用演练时为空
+    planned: Option<PlannedWrites>,
 }
 
 /// 客户端配置选项
@@ -24,6 +31,8 @@ pub struct Central {
 pub struct CentralOptions {
     pub base_url: Option<String>,
     pub timeout: Option<Duration>,
+    /// 演练模式：写请求不发送，只记录预期变更
+    pub dry_run: bool,
 }
 
 impl Central {
@@ -46,11 +55,95 @@ impl Central {
             base_url,
             token: token.into(),
             http_client,
+            planned: opts.dry_run.then(PlannedWrites::default),
         }
     }
 
-    /// 执行 HTTP 请求
-    pub(crate) async fn request<T: serde::de::DeserializeOwned>(
+    /// API 地址
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// 是否为演练模式
+    pub fn is_dry_run(&self) -> bool {
+        self.planned.is_some()
+    }
+
+    /// 设置演练模式：写请求（POST / DELETE）不发送，先读取当前状态，记录预期变更，
+    /// 并按预期状态合成返回值。每次启用都使用新的记录，与原客户端互不影响
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.planned = dry_run.then(PlannedWrites::default);
+        self
+    }
+
+    /// 进入演练模式并使用给定的记录，多个客户端可共享同一份记录以保持写请求的发送顺序
+    pub(crate) fn with_planned(mut self, planned: PlannedWrites) -> Self {
+        self.planned = Some(planned);
+        self
+    }
+
+    /// 演练模式下已记录的写请求（按发送顺序）
+    pub fn planned_writes(&self) -> Vec<PlannedWrite> {
+        self.planned.as_ref().map(PlannedWrites::list).unwrap_or_default()
+    }
+
+    /// 取出并清空已记录的写请求
+    pub fn take_planned_writes(&self) -> Vec<PlannedWrite> {
+        self.planned.as_ref().map(PlannedWrites::take).unwrap_or_default()
+    }
+
+    /// 演练模式下记录其他客户端（如批量操作中逐个成员使用的客户端）的写请求
+    pub(crate) fn record_planned(&self, writes: Vec<PlannedWrite>) {
+        if let Some(planned) = &self.planned {
+            planned.extend(writes);
+        }
+    }
+
+    /// 执行 HTTP 请求，演练模式下写请求只记录预期变更，返回按预期状态合成的结果
+    pub(crate) async fn request<T: serde::de::DeserializeOwned + schemars::JsonSchema>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&impl serde::Serialize>,
+    ) -> Result<T, Error> {
+        if self.planned.is_some() && method != reqwest::Method::GET {
+            let body = body.map(serde_json::to_value).transpose()?;
+            let write = self.plan_write(method, path, body).await?;
+            return Ok(dry_run::synthesize(&write)?);
+        }
+        self.send(method, path, body).await
+    }
+
+    /// `POST /network` 创建网络，写入的是集合，网络 ID 由 Central 生成
+    fn creates_resource(method: &reqwest::Method, path: &str) -> bool {
+        *method == reqwest::Method::POST && path == "/network"
+    }
+
+    /// 演练：读取目标资源的当前状态，计算并记录写入后的预期变更；
+    /// 创建请求与演练中新建的资源（路径含占位 ID）没有可读取的当前状态
+    async fn plan_write(&self, method: reqwest::Method, path: &str, body: Option<serde_json::Value>) -> Result<PlannedWrite, Error> {
+        let create = Self::creates_resource(&method, path);
+        let before = if create || path.contains(dry_run::PLACEHOLDER_ID) {
+            None
+        } else {
+            match self.send::<serde_json::Value>(reqwest::Method::GET, path, None::<&()>).await {
+                Ok(value) => value.is_object().then_some(value),
+                Err(Error::Api { status: 404, .. }) => None,
+                Err(err) => return Err(err),
+            }
+        };
+        let mut write = PlannedWrite::new(&method, path, body, before);
+        if create {
+            write = write.with_placeholder_id();
+        }
+        if let Some(planned) = &self.planned {
+            planned.push(write.clone());
+        }
+        Ok(write)
+    }
+
+    /// 发送 HTTP 请求
+    async fn send<T: serde::de::DeserializeOwned>(
         &self,
         method: reqwest::Method,
         path: &str,
@@ -101,6 +194,10 @@ impl Central {
         method: reqwest::Method,
         path: &str,
     ) -> Result<(), Error> {
+        if self.planned.is_some() {
+            return self.plan_write(method, path, None).await.map(drop);
+        }
+
         let url = format!("{}{}", self.base_url, path);
 
         let mut headers = HeaderMap::new();
@@ -139,4 +236,7 @@ pub enum Error {
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("Invalid client version: {0}")]
+    InvalidVersion(String),
 }
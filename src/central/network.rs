@@ -1,4 +1,4 @@
-use super::{Central, Error, Network, CreateNetworkConfig, CreateNetworkRequest, MemberService};
+use super::{AssignMode, Central, CreateNetworkConfig, CreateNetworkRequest, Dns, Error, IpAssignmentPool, MemberService, Network, Route, UpdateNetworkRequest};
 
 /// 网络管理服务
 pub struct NetworkService {
@@ -26,12 +26,30 @@ impl NetworkService {
         self.client.request(reqwest::Method::POST, "/network", Some(&req)).await
     }
 
+    /// 创建新网络（可同时设置描述与规则源）
+    pub async fn create_with(&self, req: &UpdateNetworkRequest) -> Result<Network, Error> {
+        self.client.request(reqwest::Method::POST, "/network", Some(req)).await
+    }
+
     /// 更新网络配置
     pub async fn update(&self, network_id: &str, config: &CreateNetworkConfig) -> Result<Network, Error> {
         let req = CreateNetworkRequest { config: Some(config.clone()) };
         self.client.request(reqwest::Method::POST, &format!("/network/{}", network_id), Some(&req)).await
     }
 
+    /// 更新网络（配置、描述与规则源）
+    pub async fn update_with(&self, network_id: &str, req: &UpdateNetworkRequest) -> Result<Network, Error> {
+        self.client.request(reqwest::Method::POST, &format!("/network/{}", network_id), Some(req)).await
+    }
+
+    /// 更新流规则源
+    pub async fn update_rules_source(&self, network_id: &str, rules_source: impl Into<String>) -> Result<Network, Error> {
+        self.update_with(network_id, &UpdateNetworkRequest {
+            rules_source: Some(rules_source.into()),
+            ..Default::default()
+        }).await
+    }
+
     /// 删除网络
     pub async fn delete(&self, network_id: &str) -> Result<(), Error> {
         self.client.request_empty(reqwest::Method::DELETE, &format!("/network/{}", network_id)).await
@@ -45,12 +63,10 @@ impl NetworkService {
 
 /// 网络配置构建器
 #[derive(Default)]
-#[allow(dead_code)]
 pub struct NetworkConfigBuilder {
     config: CreateNetworkConfig,
 }
 
-#[allow(dead_code)]
 impl NetworkConfigBuilder {
     pub fn new() -> Self {
         Self::default()
@@ -76,6 +92,51 @@ impl NetworkConfigBuilder {
         self
     }
 
+    pub fn multicast_limit(mut self, v: i32) -> Self {
+        self.config.multicast_limit = Some(v);
+        self
+    }
+
+    pub fn routes(mut self, routes: Vec<Route>) -> Self {
+        self.config.routes = Some(routes);
+        self
+    }
+
+    /// 追加一条路由（`via` 为空表示受管路由）
+    pub fn route(mut self, target: impl Into<String>, via: Option<String>) -> Self {
+        self.config.routes.get_or_insert_with(Vec::new).push(Route { target: target.into(), via });
+        self
+    }
+
+    pub fn ip_assignment_pools(mut self, pools: Vec<IpAssignmentPool>) -> Self {
+        self.config.ip_assignment_pools = Some(pools);
+        self
+    }
+
+    /// 追加一个 IP 分配池
+    pub fn ip_assignment_pool(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.config.ip_assignment_pools.get_or_insert_with(Vec::new).push(IpAssignmentPool {
+            ip_range_start: start.into(),
+            ip_range_end: end.into(),
+        });
+        self
+    }
+
+    pub fn v4_assign_mode(mut self, mode: AssignMode) -> Self {
+        self.config.v4_assign_mode = Some(mode);
+        self
+    }
+
+    pub fn v6_assign_mode(mut self, mode: AssignMode) -> Self {
+        self.config.v6_assign_mode = Some(mode);
+        self
+    }
+
+    pub fn dns(mut self, dns: Dns) -> Self {
+        self.config.dns = Some(dns);
+        self
+    }
+
     pub fn build(self) -> CreateNetworkConfig {
         self.config
     }
@@ -0,0 +1,466 @@
+//! 成员查询：在 `MemberService::list` 的结果上筛选、排序与截取
+
+use super::{Error, Member};
+use crate::setup::Cidr;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 成员筛选条件，所有已设置的条件同时满足才算匹配
+#[derive(Debug, Clone, Default)]
+pub struct MemberFilter {
+    member_ids: Vec<String>,
+    authorized: Option<bool>,
+    name: Option<String>,
+    name_regex: Option<Regex>,
+    description_regex: Option<Regex>,
+    ip_in: Option<Cidr>,
+    seen_within: Option<Duration>,
+    seen_after: Option<i64>,
+    seen_before: Option<i64>,
+    min_client_version: Option<ClientVersion>,
+    max_client_version: Option<ClientVersion>,
+    physical_address: Option<String>,
+    active_bridge: Option<bool>,
+    tags: Vec<(i32, Option<i32>)>,
+}
+
+impl MemberFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 只选择指定 ID 的成员
+    pub fn member_ids<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.member_ids = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 按授权状态筛选
+    pub fn authorized(mut self, authorized: bool) -> Self {
+        self.authorized = Some(authorized);
+        self
+    }
+
+    /// 名称匹配通配符模式（`*` 任意字符序列，`?` 单个字符）
+    pub fn name_matches(mut self, pattern: impl Into<String>) -> Self {
+        self.name = Some(pattern.into());
+        self
+    }
+
+    /// 名称匹配正则表达式
+    pub fn name_regex(mut self, regex: Regex) -> Self {
+        self.name_regex = Some(regex);
+        self
+    }
+
+    /// 描述匹配正则表达式
+    pub fn description_regex(mut self, regex: Regex) -> Self {
+        self.description_regex = Some(regex);
+        self
+    }
+
+    /// 至少一个指定 IP 属于该网段
+    pub fn ip_in(mut self, cidr: Cidr) -> Self {
+        self.ip_in = Some(cidr);
+        self
+    }
+
+    /// 最近一段时间内在线过
+    pub fn seen_within(mut self, duration: Duration) -> Self {
+        self.seen_within = Some(duration);
+        self
+    }
+
+    /// 最后在线时间不早于该时间（毫秒时间戳）
+    pub fn seen_after(mut self, timestamp_ms: i64) -> Self {
+        self.seen_after = Some(timestamp_ms);
+        self
+    }
+
+    /// 最后在线时间早于该时间（毫秒时间戳），从未在线的成员也算
+    pub fn seen_before(mut self, timestamp_ms: i64) -> Self {
+        self.seen_before = Some(timestamp_ms);
+        self
+    }
+
+    /// 客户端版本不低于该版本，版本未知的成员不匹配
+    pub fn min_client_version(mut self, version: ClientVersion) -> Self {
+        self.min_client_version = Some(version);
+        self
+    }
+
+    /// 客户端版本不高于该版本（含），版本未知的成员不匹配
+    pub fn max_client_version(mut self, version: ClientVersion) -> Self {
+        self.max_client_version = Some(version);
+        self
+    }
+
+    /// 物理地址匹配通配符模式（如 `203.0.113.*`）
+    pub fn physical_address(mut self, pattern: impl Into<String>) -> Self {
+        self.physical_address = Some(pattern.into());
+        self
+    }
+
+    /// 按是否允许作为以太网桥筛选
+    pub fn active_bridge(mut self, active_bridge: bool) -> Self {
+        self.active_bridge = Some(active_bridge);
+        self
+    }
+
+    /// 带有该标签且值相同，可多次调用
+    pub fn tag(mut self, id: i32, value: i32) -> Self {
+        self.tags.push((id, Some(value)));
+        self
+    }
+
+    /// 带有该标签（任意值），可多次调用
+    pub fn has_tag(mut self, id: i32) -> Self {
+        self.tags.push((id, None));
+        self
+    }
+
+    /// 未设置任何条件（匹配全部成员）
+    pub fn is_empty(&self) -> bool {
+        self.member_ids.is_empty()
+            && self.authorized.is_none()
+            && self.name.is_none()
+            && self.name_regex.is_none()
+            && self.description_regex.is_none()
+            && self.ip_in.is_none()
+            && self.seen_within.is_none()
+            && self.seen_after.is_none()
+            && self.seen_before.is_none()
+            && self.min_client_version.is_none()
+            && self.max_client_version.is_none()
+            && self.physical_address.is_none()
+            && self.active_bridge.is_none()
+            && self.tags.is_empty()
+    }
+
+    /// 成员是否匹配；`now_ms` 为当前时间（毫秒时间戳）
+    pub fn matches(&self, member: &Member, now_ms: i64) -> bool {
+        let config = member.config.as_ref();
+        if !self.member_ids.is_empty() && !self.member_ids.iter().any(|id| id.eq_ignore_ascii_case(&member.node_id)) {
+            return false;
+        }
+        if self.authorized.is_some_and(|a| config.is_some_and(|c| c.authorized) != a) {
+            return false;
+        }
+        if self.active_bridge.is_some_and(|b| config.is_some_and(|c| c.active_bridge) != b) {
+            return false;
+        }
+        if self.name.as_deref().is_some_and(|p| !glob_match(p, &member.name)) {
+            return false;
+        }
+        if self.name_regex.as_ref().is_some_and(|r| !r.is_match(&member.name)) {
+            return false;
+        }
+        if self.description_regex.as_ref().is_some_and(|r| !r.is_match(&member.description)) {
+            return false;
+        }
+        if let Some(cidr) = &self.ip_in {
+            let ips = config.map(|c| c.ip_assignments.as_slice()).unwrap_or_default();
+            if !ips.iter().filter_map(|ip| ip.parse::<IpAddr>().ok()).any(|ip| cidr.contains(ip)) {
+                return false;
+            }
+        }
+        if let Some(window) = self.seen_within {
+            if member.last_seen <= 0 || now_ms - member.last_seen > window.as_millis() as i64 {
+                return false;
+            }
+        }
+        if self.seen_after.is_some_and(|t| member.last_seen < t) || self.seen_before.is_some_and(|t| member.last_seen >= t) {
+            return false;
+        }
+        if self.min_client_version.is_some() || self.max_client_version.is_some() {
+            let Some(version) = parse_version(&member.client_version).map(ClientVersion) else {
+                return false;
+            };
+            if self.min_client_version.is_some_and(|min| version < min)
+                || self.max_client_version.is_some_and(|max| version > max)
+            {
+                return false;
+            }
+        }
+        if self.physical_address.as_deref().is_some_and(|p| !glob_match(p, &member.physical_address)) {
+            return false;
+        }
+        let tags = config.map(|c| c.tags.as_slice()).unwrap_or_default();
+        self.tags.iter().all(|&(id, value)| tags.iter().any(|&(t, v)| t == id && value.is_none_or(|value| v == value)))
+    }
+
+    /// 按条件筛选成员列表
+    pub fn apply(&self, members: Vec<Member>) -> Vec<Member> {
+        let now = now_ms();
+        members.into_iter().filter(|m| self.matches(m, now)).collect()
+    }
+}
+
+/// 客户端版本号（`主.次.修订`，缺少的部分视为 0），通过 `"1.12".parse()` 创建
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClientVersion([u32; 3]);
+
+impl FromStr for ClientVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_version(s).map(Self).ok_or_else(|| Error::InvalidVersion(s.to_string()))
+    }
+}
+
+impl fmt::Display for ClientVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [major, minor, patch] = self.0;
+        write!(f, "{}.{}.{}", major, minor, patch)
+    }
+}
+
+/// 排序字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberSort {
+    /// 名称（不区分大小写）
+    Name,
+    /// 成员 ID
+    NodeId,
+    /// 最后在线时间
+    LastSeen,
+    /// 客户端版本，版本未知的排在最前
+    ClientVersion,
+    /// 第一个指定 IP，没有 IP 的排在最前
+    Ip,
+}
+
+/// 查询结果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MemberQueryResult {
+    /// 匹配条件的成员总数（截取前）
+    pub total: usize,
+    /// 排序并截取后的成员
+    pub members: Vec<Member>,
+}
+
+/// 成员查询：筛选、排序与分页
+///
+/// ```rust,no_run
+/// # use zerotier_sdk_rust_mcp::central::{Central, MemberFilter, MemberQuery, MemberSort};
+/// # async fn run(central: Central) -> Result<(), zerotier_sdk_rust_mcp::central::Error> {
+/// let query = MemberQuery::new()
+///     .filter(MemberFilter::new().authorized(true).max_client_version("1.12".parse()?))
+///     .sort_by(MemberSort::LastSeen)
+///     .descending(true)
+///     .limit(20);
+/// let result = central.networks().members("8056c2e21c000001").find(&query).await?;
+/// println!("{} of {} members", result.members.len(), result.total);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemberQuery {
+    filter: MemberFilter,
+    sort: Option<MemberSort>,
+    descending: bool,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl MemberQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置筛选条件
+    pub fn filter(mut self, filter: MemberFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// 排序字段，未设置时按成员 ID 排序
+    pub fn sort_by(mut self, sort: MemberSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// 是否降序
+    pub fn descending(mut self, descending: bool) -> Self {
+        self.descending = descending;
+        self
+    }
+
+    /// 跳过前 `offset` 个结果
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// 最多返回的成员数
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 对成员列表执行查询
+    pub fn apply(&self, members: Vec<Member>) -> MemberQueryResult {
+        let mut members = self.filter.apply(members);
+        members.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        if let Some(sort) = self.sort {
+            members.sort_by(|a, b| compare(sort, a, b));
+        }
+        if self.descending {
+            members.reverse();
+        }
+        let total = members.len();
+        let members = members.into_iter().skip(self.offset).take(self.limit.unwrap_or(usize::MAX)).collect();
+        MemberQueryResult { total, members }
+    }
+}
+
+fn compare(sort: MemberSort, a: &Member, b: &Member) -> Ordering {
+    match sort {
+        MemberSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        MemberSort::NodeId => a.node_id.cmp(&b.node_id),
+        MemberSort::LastSeen => a.last_seen.cmp(&b.last_seen),
+        MemberSort::ClientVersion => parse_version(&a.client_version).cmp(&parse_version(&b.client_version)),
+        MemberSort::Ip => first_ip(a).cmp(&first_ip(b)),
+    }
+}
+
+fn first_ip(member: &Member) -> Option<IpAddr> {
+    member.config.as_ref()?.ip_assignments.iter().find_map(|ip| ip.parse().ok())
+}
+
+/// 解析 `主.次.修订` 版本号，缺少的部分视为 0
+pub(crate) fn parse_version(version: &str) -> Option<[u32; 3]> {
+    let mut parts = [0; 3];
+    for (i, part) in version.trim().split('.').enumerate() {
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        *parts.get_mut(i)? = part.parse().ok()?;
+    }
+    Some(parts)
+}
+
+/// 通配符匹配：`*` 匹配任意字符序列，`?` 匹配单个字符
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    fn member(id: &str, name: &str, version: &str, last_seen: i64) -> Member {
+        serde_json::from_value(json!({
+            "nodeId": id,
+            "name": name,
+            "config": { "authorized": true, "creationTime": 0, "ipAssignments": ["10.147.20.5"], "tags": [[1, 2]] },
+            "lastOnline": last_seen,
+            "lastSeen": last_seen,
+            "clientVersion": version,
+            "protocolVersion": 12,
+            "supportsRulesEngine": true,
+        }))
+        .unwrap()
+    }
+
+    fn version(s: &str) -> ClientVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn client_version_rejects_malformed_input() {
+        for s in ["", "abc", "1.x", "1..2", "1.12.", "1.2.3.4", "+1.2", "-1"] {
+            assert!(matches!(s.parse::<ClientVersion>(), Err(Error::InvalidVersion(_))), "{s:?}");
+        }
+        assert_eq!(version(" 1.12 ").to_string(), "1.12.0");
+        assert!(version("1.12") < version("1.12.1"));
+    }
+
+    #[test]
+    fn client_version_bounds_are_inclusive() {
+        let filter = MemberFilter::new().min_client_version(version("1.12")).max_client_version(version("1.14.2"));
+        assert!(!filter.matches(&member("a", "", "1.10.6", NOW), NOW));
+        assert!(filter.matches(&member("a", "", "1.12.0", NOW), NOW));
+        assert!(filter.matches(&member("a", "", "1.14.2", NOW), NOW));
+        assert!(!filter.matches(&member("a", "", "1.14.3", NOW), NOW));
+    }
+
+    #[test]
+    fn unknown_client_version_never_matches_a_version_bound() {
+        let unknown = member("a", "", "", NOW);
+        assert!(!MemberFilter::new().min_client_version(version("0")).matches(&unknown, NOW));
+        assert!(!MemberFilter::new().max_client_version(version("99")).matches(&unknown, NOW));
+    }
+
+    #[test]
+    fn seen_window_boundaries() {
+        let filter = MemberFilter::new().seen_within(Duration::from_secs(60));
+        assert!(filter.matches(&member("a", "", "", NOW - 60_000), NOW));
+        assert!(!filter.matches(&member("a", "", "", NOW - 60_001), NOW));
+        assert!(!filter.matches(&member("a", "", "", 0), NOW));
+
+        let after = MemberFilter::new().seen_after(NOW);
+        assert!(after.matches(&member("a", "", "", NOW), NOW));
+        assert!(!after.matches(&member("a", "", "", NOW - 1), NOW));
+        let before = MemberFilter::new().seen_before(NOW);
+        assert!(!before.matches(&member("a", "", "", NOW), NOW));
+        assert!(before.matches(&member("a", "", "", 0), NOW));
+    }
+
+    #[test]
+    fn all_conditions_must_match() {
+        let m = member("abcdef0123", "ci-runner-1", "1.14.0", NOW);
+        assert!(MemberFilter::new().matches(&m, NOW));
+        assert!(MemberFilter::new().member_ids(["ABCDEF0123"]).matches(&m, NOW));
+        assert!(MemberFilter::new().name_matches("ci-*-?").ip_in("10.147.20.0/24".parse().unwrap()).tag(1, 2).matches(&m, NOW));
+        assert!(!MemberFilter::new().name_matches("ci-*").authorized(false).matches(&m, NOW));
+        assert!(!MemberFilter::new().ip_in("10.147.21.0/24".parse().unwrap()).matches(&m, NOW));
+        assert!(MemberFilter::new().has_tag(1).matches(&m, NOW));
+        assert!(!MemberFilter::new().tag(1, 3).matches(&m, NOW));
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("ci-*", "ci-"));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(!glob_match("ci-*", "ci"));
+    }
+}
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 /// Central API 状态
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CentralStatus {
     #[serde(default, deserialize_with = "null_default")]
@@ -23,7 +24,7 @@ pub struct CentralStatus {
 }
 
 /// 状态中的用户信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StatusUser {
     #[serde(default, deserialize_with = "null_default")]
@@ -37,7 +38,7 @@ pub struct StatusUser {
 }
 
 /// 网络信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Network {
     #[serde(default, deserialize_with = "null_default")]
@@ -60,7 +61,7 @@ pub struct Network {
 }
 
 /// 网络配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkConfig {
     #[serde(default, deserialize_with = "null_default")]
@@ -80,10 +81,30 @@ pub struct NetworkConfig {
     pub v4_assign_mode: Option<AssignMode>,
     pub v6_assign_mode: Option<AssignMode>,
     pub dns: Option<Dns>,
+    /// 由规则源编译得到的流规则
+    #[serde(default, deserialize_with = "null_default")]
+    pub rules: Vec<serde_json::Value>,
+    pub sso_config: Option<SsoConfig>,
+}
+
+/// SSO 配置
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SsoConfig {
+    #[serde(default, deserialize_with = "null_default")]
+    pub enabled: bool,
+    #[serde(default, deserialize_with = "null_default")]
+    pub mode: String,
+    #[serde(default, deserialize_with = "null_default")]
+    pub client_id: String,
+    #[serde(default, deserialize_with = "null_default")]
+    pub issuer: String,
+    #[serde(default, deserialize_with = "null_default")]
+    pub provider: String,
 }
 
 /// 路由配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Route {
     pub target: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -91,7 +112,7 @@ pub struct Route {
 }
 
 /// IP 分配池
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IpAssignmentPool {
     pub ip_range_start: String,
@@ -99,7 +120,7 @@ pub struct IpAssignmentPool {
 }
 
 /// IP 分配模式
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AssignMode {
     pub zt: bool,
     #[serde(default)]
@@ -109,7 +130,7 @@ pub struct AssignMode {
 }
 
 /// DNS 配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Dns {
     pub domain: String,
     #[serde(default, deserialize_with = "null_default")]
@@ -117,7 +138,7 @@ pub struct Dns {
 }
 
 /// 网络成员
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Member {
     #[serde(default, deserialize_with = "null_default")]
@@ -142,7 +163,7 @@ pub struct Member {
 }
 
 /// 成员配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MemberConfig {
     #[serde(default, deserialize_with = "null_default")]
@@ -156,16 +177,30 @@ pub struct MemberConfig {
     pub ip_assignments: Vec<String>,
     #[serde(default, deserialize_with = "null_default")]
     pub sso_exempt: bool,
+    #[serde(default, deserialize_with = "null_default")]
+    pub tags: Vec<(i32, i32)>,
 }
 
 /// 创建网络请求
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CreateNetworkRequest {
     pub config: Option<CreateNetworkConfig>,
 }
 
+/// 更新网络请求（含描述与规则源）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNetworkRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<CreateNetworkConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules_source: Option<String>,
+}
+
 /// 创建网络配置
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateNetworkConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -191,7 +226,7 @@ pub struct CreateNetworkConfig {
 }
 
 /// 更新成员请求
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct UpdateMemberRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -202,7 +237,7 @@ pub struct UpdateMemberRequest {
 }
 
 /// 更新成员配置
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateMemberConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -213,6 +248,41 @@ pub struct UpdateMemberConfig {
     pub no_auto_assign_ips: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "null_default_opt")]
     pub ip_assignments: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", deserialize_with = "null_default_opt")]
+    pub tags: Option<Vec<(i32, i32)>>,
+}
+
+impl From<&NetworkConfig> for CreateNetworkConfig {
+    fn from(c: &NetworkConfig) -> Self {
+        Self {
+            name: Some(c.name.clone()),
+            private: Some(c.private),
+            enable_broadcast: Some(c.enable_broadcast),
+            mtu: Some(c.mtu),
+            multicast_limit: Some(c.multicast_limit),
+            routes: Some(c.routes.clone()),
+            ip_assignment_pools: Some(c.ip_assignment_pools.clone()),
+            v4_assign_mode: c.v4_assign_mode.clone(),
+            v6_assign_mode: c.v6_assign_mode.clone(),
+            dns: c.dns.clone(),
+        }
+    }
+}
+
+impl From<&Member> for UpdateMemberRequest {
+    fn from(m: &Member) -> Self {
+        Self {
+            name: Some(m.name.clone()),
+            description: Some(m.description.clone()),
+            config: m.config.as_ref().map(|c| UpdateMemberConfig {
+                authorized: Some(c.authorized),
+                active_bridge: Some(c.active_bridge),
+                no_auto_assign_ips: Some(c.no_auto_assign_ips),
+                ip_assignments: Some(c.ip_assignments.clone()),
+                tags: Some(c.tags.clone()),
+            }),
+        }
+    }
 }
 
 fn null_default<'de, T, D>(deserializer: D) -> Result<T, D::Error>
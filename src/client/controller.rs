@@ -2,6 +2,7 @@ use super::{Client, Error, ControllerStatus, ControllerNetwork, ControllerNetwor
 use serde_json::json;
 
 /// 控制器管理服务（自托管时可用）
+#[derive(Clone)]
 pub struct ControllerService {
     client: Client,
 }
@@ -11,6 +12,10 @@ impl ControllerService {
         Self { client }
     }
 
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+
     /// 获取控制器状态
     pub async fn status(&self) -> Result<ControllerStatus, Error> {
         self.client.request(reqwest::Method::GET, "/controller", None::<&()>).await
@@ -43,9 +48,14 @@ impl ControllerService {
         self.client.request_empty(reqwest::Method::DELETE, &format!("/controller/network/{}", network_id)).await
     }
 
-    /// 列出网络成员
+    /// 列出网络成员 ID
     pub async fn list_members(&self, network_id: &str) -> Result<Vec<String>, Error> {
-        self.client.request(reqwest::Method::GET, &format!("/controller/network/{}/member", network_id), None::<&()>).await
+        // 控制器返回 `{ "成员ID": 修订号 }`，兼容返回数组的实现
+        let value: serde_json::Value = self.client.request(reqwest::Method::GET, &format!("/controller/network/{}/member", network_id), None::<&()>).await?;
+        Ok(match value {
+            serde_json::Value::Object(map) => map.into_iter().map(|(id, _)| id).collect(),
+            other => serde_json::from_value(other)?,
+        })
     }
 
     /// 获取成员信息
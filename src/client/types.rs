@@ -1,7 +1,8 @@
-use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// 节点状态
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NodeStatus {
     pub address: String,
@@ -15,7 +16,7 @@ pub struct NodeStatus {
 }
 
 /// 本地网络信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Network {
     pub id: String,
@@ -30,6 +31,10 @@ pub struct Network {
     pub port_device_name: String,
     pub netconf_revision: i32,
     pub assigned_addresses: Vec<String>,
+    /// 受管路由
+    #[serde(default)]
+    pub routes: Vec<Route>,
+    #[serde(rename = "allowDNS")]
     pub allow_dns: bool,
     pub allow_default: bool,
     pub allow_global: bool,
@@ -38,7 +43,7 @@ pub struct Network {
 }
 
 /// DNS 配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Dns {
     pub domain: String,
     #[serde(default)]
@@ -46,10 +51,10 @@ pub struct Dns {
 }
 
 /// 网络设置（用于更新）
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkSettings {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "allowDNS", skip_serializing_if = "Option::is_none")]
     pub allow_dns: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_default: Option<bool>,
@@ -60,7 +65,7 @@ pub struct NetworkSettings {
 }
 
 /// 节点信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Peer {
     pub address: String,
     pub version: String,
@@ -70,7 +75,7 @@ pub struct Peer {
 }
 
 /// 节点路径
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PeerPath {
     pub active: bool,
@@ -83,7 +88,7 @@ pub struct PeerPath {
 }
 
 /// 控制器状态
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ControllerStatus {
     pub controller: bool,
@@ -92,7 +97,7 @@ pub struct ControllerStatus {
 }
 
 /// 控制器网络配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ControllerNetwork {
     pub id: String,
@@ -108,10 +113,20 @@ pub struct ControllerNetwork {
     pub ip_assignment_pools: Vec<IpAssignmentPool>,
     pub v4_assign_mode: Option<AssignMode>,
     pub v6_assign_mode: Option<AssignMode>,
+    #[serde(default)]
+    pub mtu: Option<i32>,
+    #[serde(default, deserialize_with = "object_or_none")]
+    pub dns: Option<Dns>,
+    /// 编译后的流规则
+    #[serde(default)]
+    pub rules: Vec<serde_json::Value>,
+    /// 规则源（控制器仅存储，不编译）
+    #[serde(default)]
+    pub rules_source: Option<String>,
 }
 
 /// 路由配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Route {
     pub target: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -119,7 +134,7 @@ pub struct Route {
 }
 
 /// IP 分配池
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IpAssignmentPool {
     pub ip_range_start: String,
@@ -127,13 +142,17 @@ pub struct IpAssignmentPool {
 }
 
 /// IP 分配模式
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AssignMode {
     pub zt: bool,
+    #[serde(default)]
+    pub rfc4193: bool,
+    #[serde(rename = "6plane", default)]
+    pub n6plane: bool,
 }
 
 /// 控制器成员
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ControllerMember {
     pub id: String,
@@ -148,10 +167,12 @@ pub struct ControllerMember {
     pub creation_time: i64,
     pub last_authorized_time: i64,
     pub last_deauthorized_time: i64,
+    #[serde(default)]
+    pub tags: Vec<(i32, i32)>,
 }
 
 /// 创建/更新网络的配置
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ControllerNetworkConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -161,6 +182,8 @@ pub struct ControllerNetworkConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enable_broadcast: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub multicast_limit: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub routes: Option<Vec<Route>>,
@@ -170,10 +193,16 @@ pub struct ControllerNetworkConfig {
     pub v4_assign_mode: Option<AssignMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub v6_assign_mode: Option<AssignMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns: Option<Dns>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules_source: Option<String>,
 }
 
 /// 成员配置
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ControllerMemberConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -184,4 +213,16 @@ pub struct ControllerMemberConfig {
     pub ip_assignments: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub no_auto_assign_ips: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<(i32, i32)>>,
+}
+
+/// 旧版控制器以 `[]` 表示未配置的对象，解析失败时视为 `None`
+fn object_or_none<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: serde::de::DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
 }
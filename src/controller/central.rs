@@ -0,0 +1,136 @@
+use super::{Backend, Capabilities, Error, MemberInfo, MemberUpdate, NetworkController, NetworkInfo, NetworkUpdate};
+use crate::central::{Central, CreateNetworkConfig, Member, Network, UpdateMemberConfig, UpdateMemberRequest, UpdateNetworkRequest};
+
+impl From<&Network> for NetworkInfo {
+    fn from(n: &Network) -> Self {
+        let cfg = n.config.as_ref();
+        Self {
+            id: n.id.clone(),
+            name: cfg.map(|c| c.name.clone()).unwrap_or_default(),
+            description: Some(n.description.clone()),
+            private: cfg.map(|c| c.private).unwrap_or(true),
+            enable_broadcast: cfg.map(|c| c.enable_broadcast).unwrap_or(true),
+            mtu: cfg.map(|c| c.mtu),
+            multicast_limit: cfg.map(|c| c.multicast_limit).unwrap_or_default(),
+            routes: cfg.map(|c| c.routes.clone()).unwrap_or_default(),
+            ip_assignment_pools: cfg.map(|c| c.ip_assignment_pools.clone()).unwrap_or_default(),
+            v4_assign_mode: cfg.and_then(|c| c.v4_assign_mode.clone()),
+            v6_assign_mode: cfg.and_then(|c| c.v6_assign_mode.clone()),
+            dns: cfg.and_then(|c| c.dns.clone()),
+            rules_source: Some(n.rules_source.clone()),
+            rules: cfg.map(|c| c.rules.clone()).unwrap_or_default(),
+            sso_enabled: cfg.and_then(|c| c.sso_config.as_ref()).map(|s| s.enabled).unwrap_or(false),
+            creation_time: cfg.map(|c| c.creation_time).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&Member> for MemberInfo {
+    fn from(m: &Member) -> Self {
+        let cfg = m.config.as_ref();
+        Self {
+            id: m.node_id.clone(),
+            network_id: m.network_id.clone(),
+            name: Some(m.name.clone()),
+            description: Some(m.description.clone()),
+            authorized: cfg.map(|c| c.authorized).unwrap_or(false),
+            active_bridge: cfg.map(|c| c.active_bridge).unwrap_or(false),
+            no_auto_assign_ips: cfg.map(|c| c.no_auto_assign_ips).unwrap_or(false),
+            ip_assignments: cfg.map(|c| c.ip_assignments.clone()).unwrap_or_default(),
+            tags: cfg.map(|c| c.tags.clone()).unwrap_or_default(),
+            last_seen: Some(m.last_seen),
+            creation_time: cfg.map(|c| c.creation_time).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&NetworkUpdate> for UpdateNetworkRequest {
+    fn from(u: &NetworkUpdate) -> Self {
+        let config = CreateNetworkConfig {
+            name: u.name.clone(),
+            private: u.private,
+            enable_broadcast: u.enable_broadcast,
+            mtu: u.mtu,
+            multicast_limit: u.multicast_limit,
+            routes: u.routes.clone(),
+            ip_assignment_pools: u.ip_assignment_pools.clone(),
+            v4_assign_mode: u.v4_assign_mode.clone(),
+            v6_assign_mode: u.v6_assign_mode.clone(),
+            dns: u.dns.clone(),
+        };
+        Self {
+            config: Some(config),
+            description: u.description.clone(),
+            rules_source: u.rules_source.clone(),
+        }
+    }
+}
+
+impl From<&MemberUpdate> for UpdateMemberRequest {
+    fn from(u: &MemberUpdate) -> Self {
+        Self {
+            name: u.name.clone(),
+            description: u.description.clone(),
+            config: Some(UpdateMemberConfig {
+                authorized: u.authorized,
+                active_bridge: u.active_bridge,
+                no_auto_assign_ips: u.no_auto_assign_ips,
+                ip_assignments: u.ip_assignments.clone(),
+                tags: u.tags.clone(),
+            }),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkController for Central {
+    fn backend(&self) -> Backend {
+        Backend::Central
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            member_names: true,
+            descriptions: true,
+            rules_source: true,
+            compiled_rules: false,
+            sso: true,
+        }
+    }
+
+    async fn list_networks(&self) -> Result<Vec<NetworkInfo>, Error> {
+        Ok(self.networks().list().await?.iter().map(Into::into).collect())
+    }
+
+    async fn get_network(&self, network_id: &str) -> Result<NetworkInfo, Error> {
+        Ok((&self.networks().get(network_id).await?).into())
+    }
+
+    async fn create_network(&self, update: &NetworkUpdate) -> Result<NetworkInfo, Error> {
+        Ok((&self.networks().create_with(&update.into()).await?).into())
+    }
+
+    async fn update_network(&self, network_id: &str, update: &NetworkUpdate) -> Result<NetworkInfo, Error> {
+        Ok((&self.networks().update_with(network_id, &update.into()).await?).into())
+    }
+
+    async fn delete_network(&self, network_id: &str) -> Result<(), Error> {
+        Ok(self.networks().delete(network_id).await?)
+    }
+
+    async fn list_members(&self, network_id: &str) -> Result<Vec<MemberInfo>, Error> {
+        Ok(self.networks().members(network_id).list().await?.iter().map(Into::into).collect())
+    }
+
+    async fn get_member(&self, network_id: &str, member_id: &str) -> Result<MemberInfo, Error> {
+        Ok((&self.networks().members(network_id).get(member_id).await?).into())
+    }
+
+    async fn update_member(&self, network_id: &str, member_id: &str, update: &MemberUpdate) -> Result<MemberInfo, Error> {
+        Ok((&self.networks().members(network_id).update(member_id, &update.into()).await?).into())
+    }
+
+    async fn delete_member(&self, network_id: &str, member_id: &str) -> Result<(), Error> {
+        Ok(self.networks().members(network_id).delete(member_id).await?)
+    }
+}
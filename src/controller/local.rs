@@ -0,0 +1,186 @@
+use super::{Backend, Capabilities, Error, MemberInfo, MemberUpdate, NetworkController, NetworkInfo, NetworkUpdate};
+use crate::central::{AssignMode, Dns, IpAssignmentPool, Route};
+use crate::client::{self, ControllerMember, ControllerMemberConfig, ControllerNetwork, ControllerNetworkConfig, ControllerService};
+
+impl From<&client::Route> for Route {
+    fn from(r: &client::Route) -> Self {
+        Self { target: r.target.clone(), via: r.via.clone() }
+    }
+}
+
+impl From<&Route> for client::Route {
+    fn from(r: &Route) -> Self {
+        Self { target: r.target.clone(), via: r.via.clone() }
+    }
+}
+
+impl From<&client::IpAssignmentPool> for IpAssignmentPool {
+    fn from(p: &client::IpAssignmentPool) -> Self {
+        Self { ip_range_start: p.ip_range_start.clone(), ip_range_end: p.ip_range_end.clone() }
+    }
+}
+
+impl From<&IpAssignmentPool> for client::IpAssignmentPool {
+    fn from(p: &IpAssignmentPool) -> Self {
+        Self { ip_range_start: p.ip_range_start.clone(), ip_range_end: p.ip_range_end.clone() }
+    }
+}
+
+impl From<&client::AssignMode> for AssignMode {
+    fn from(m: &client::AssignMode) -> Self {
+        Self { zt: m.zt, rfc4193: m.rfc4193, n6plane: m.n6plane }
+    }
+}
+
+impl From<&AssignMode> for client::AssignMode {
+    fn from(m: &AssignMode) -> Self {
+        Self { zt: m.zt, rfc4193: m.rfc4193, n6plane: m.n6plane }
+    }
+}
+
+impl From<&client::Dns> for Dns {
+    fn from(d: &client::Dns) -> Self {
+        Self { domain: d.domain.clone(), servers: d.servers.clone() }
+    }
+}
+
+impl From<&Dns> for client::Dns {
+    fn from(d: &Dns) -> Self {
+        Self { domain: d.domain.clone(), servers: d.servers.clone() }
+    }
+}
+
+impl From<&ControllerNetwork> for NetworkInfo {
+    fn from(n: &ControllerNetwork) -> Self {
+        Self {
+            id: n.id.clone(),
+            name: n.name.clone(),
+            description: None,
+            private: n.private,
+            enable_broadcast: n.enable_broadcast,
+            mtu: n.mtu,
+            multicast_limit: n.multicast_limit,
+            routes: n.routes.iter().map(Into::into).collect(),
+            ip_assignment_pools: n.ip_assignment_pools.iter().map(Into::into).collect(),
+            v4_assign_mode: n.v4_assign_mode.as_ref().map(Into::into),
+            v6_assign_mode: n.v6_assign_mode.as_ref().map(Into::into),
+            dns: n.dns.as_ref().map(Into::into),
+            rules_source: n.rules_source.clone(),
+            rules: n.rules.clone(),
+            sso_enabled: false,
+            creation_time: n.creation_time,
+        }
+    }
+}
+
+impl From<&ControllerMember> for MemberInfo {
+    fn from(m: &ControllerMember) -> Self {
+        Self {
+            id: m.address.clone(),
+            network_id: m.network_id.clone(),
+            name: None,
+            description: None,
+            authorized: m.authorized,
+            active_bridge: m.active_bridge,
+            no_auto_assign_ips: m.no_auto_assign_ips,
+            ip_assignments: m.ip_assignments.clone(),
+            tags: m.tags.clone(),
+            last_seen: None,
+            creation_time: m.creation_time,
+        }
+    }
+}
+
+impl From<&NetworkUpdate> for ControllerNetworkConfig {
+    fn from(u: &NetworkUpdate) -> Self {
+        Self {
+            name: u.name.clone(),
+            private: u.private,
+            enable_broadcast: u.enable_broadcast,
+            mtu: u.mtu,
+            multicast_limit: u.multicast_limit,
+            routes: u.routes.as_ref().map(|r| r.iter().map(Into::into).collect()),
+            ip_assignment_pools: u.ip_assignment_pools.as_ref().map(|p| p.iter().map(Into::into).collect()),
+            v4_assign_mode: u.v4_assign_mode.as_ref().map(Into::into),
+            v6_assign_mode: u.v6_assign_mode.as_ref().map(Into::into),
+            dns: u.dns.as_ref().map(Into::into),
+            rules: u.rules.clone(),
+            rules_source: u.rules_source.clone(),
+        }
+    }
+}
+
+impl From<&MemberUpdate> for ControllerMemberConfig {
+    fn from(u: &MemberUpdate) -> Self {
+        Self {
+            authorized: u.authorized,
+            active_bridge: u.active_bridge,
+            ip_assignments: u.ip_assignments.clone(),
+            no_auto_assign_ips: u.no_auto_assign_ips,
+            tags: u.tags.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkController for ControllerService {
+    fn backend(&self) -> Backend {
+        Backend::SelfHosted
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            member_names: false,
+            descriptions: false,
+            rules_source: true,
+            compiled_rules: true,
+            sso: false,
+        }
+    }
+
+    async fn list_networks(&self) -> Result<Vec<NetworkInfo>, Error> {
+        let mut networks = Vec::new();
+        for id in ControllerService::list_networks(self).await? {
+            networks.push((&ControllerService::get_network(self, &id).await?).into());
+        }
+        Ok(networks)
+    }
+
+    async fn get_network(&self, network_id: &str) -> Result<NetworkInfo, Error> {
+        Ok((&ControllerService::get_network(self, network_id).await?).into())
+    }
+
+    async fn create_network(&self, update: &NetworkUpdate) -> Result<NetworkInfo, Error> {
+        let node_id = self.client().status().await?.address;
+        let config = ControllerNetworkConfig::from(update);
+        Ok((&ControllerService::create_network(self, &node_id, Some(&config)).await?).into())
+    }
+
+    async fn update_network(&self, network_id: &str, update: &NetworkUpdate) -> Result<NetworkInfo, Error> {
+        Ok((&ControllerService::update_network(self, network_id, &update.into()).await?).into())
+    }
+
+    async fn delete_network(&self, network_id: &str) -> Result<(), Error> {
+        Ok(ControllerService::delete_network(self, network_id).await?)
+    }
+
+    async fn list_members(&self, network_id: &str) -> Result<Vec<MemberInfo>, Error> {
+        let mut members = Vec::new();
+        for id in ControllerService::list_members(self, network_id).await? {
+            members.push((&ControllerService::get_member(self, network_id, &id).await?).into());
+        }
+        Ok(members)
+    }
+
+    async fn get_member(&self, network_id: &str, member_id: &str) -> Result<MemberInfo, Error> {
+        Ok((&ControllerService::get_member(self, network_id, member_id).await?).into())
+    }
+
+    async fn update_member(&self, network_id: &str, member_id: &str, update: &MemberUpdate) -> Result<MemberInfo, Error> {
+        Ok((&ControllerService::update_member(self, network_id, member_id, &update.into()).await?).into())
+    }
+
+    async fn delete_member(&self, network_id: &str, member_id: &str) -> Result<(), Error> {
+        Ok(ControllerService::delete_member(self, network_id, member_id).await?)
+    }
+}
@@ -0,0 +1,415 @@
+use super::{Error, MemberUpdate, NetworkController, NetworkInfo, NetworkUpdate};
+use serde::Serialize;
+
+/// 迁移选项
+#[derive(Debug, Clone, Default)]
+pub struct MigrateOptions {
+    /// 目标网络 ID；为空时按名称匹配目标网络，找不到则新建，同名网络有多个时返回错误
+    pub target_network_id: Option<String>,
+    /// 仅迁移已授权成员
+    pub authorized_only: bool,
+}
+
+/// 无法迁移的内容
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationIssue {
+    /// 相关成员，为空表示网络级
+    pub member_id: Option<String>,
+    pub field: String,
+    pub message: String,
+}
+
+/// 迁移结果
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub source_network_id: String,
+    pub target_network_id: String,
+    /// 目标网络为新建
+    pub created: bool,
+    /// 已迁移的成员 ID
+    pub members: Vec<String>,
+    pub issues: Vec<MigrationIssue>,
+}
+
+impl MigrationReport {
+    /// 网络 ID 发生变化时，成员需要重新加入目标网络
+    pub fn network_id_changed(&self) -> bool {
+        !self.source_network_id.eq_ignore_ascii_case(&self.target_network_id)
+    }
+}
+
+/// 在两个控制器间迁移网络配置（路由、分配池、DNS、规则）及成员的授权与 IP 分配
+///
+/// 可重复执行：目标网络已存在时原地更新。目标网络写入后某个成员失败时返回
+/// [`Error::Incomplete`]，其中带有目标网络 ID、已迁移的成员与已发现的问题。
+pub async fn migrate_network(
+    source: &dyn NetworkController,
+    target: &dyn NetworkController,
+    network_id: &str,
+    opts: &MigrateOptions,
+) -> Result<MigrationReport, Error> {
+    let network = source.get_network(network_id).await?;
+    let members = source.list_members(network_id).await?;
+    let caps = target.capabilities();
+    let mut issues = Vec::new();
+
+    let update = network_update(&network, target, &mut issues);
+
+    let existing = match &opts.target_network_id {
+        Some(id) => Some(target.get_network(id).await?),
+        None => {
+            let mut matches: Vec<NetworkInfo> =
+                target.list_networks().await?.into_iter().filter(|n| n.name == network.name).collect();
+            // 同名网络有多个时无法确定目标，新建只会再增加一个同名网络
+            if matches.len() > 1 {
+                let ids = matches.into_iter().map(|n| n.id).collect();
+                return Err(Error::AmbiguousNetwork { name: network.name.clone(), ids });
+            }
+            matches.pop()
+        }
+    };
+
+    let (target_network, created) = match existing {
+        Some(n) => (target.update_network(&n.id, &update).await?, false),
+        None => (target.create_network(&update).await?, true),
+    };
+
+    let mut report = MigrationReport {
+        source_network_id: network.id.clone(),
+        target_network_id: target_network.id,
+        created,
+        members: Vec::new(),
+        issues,
+    };
+    for member in members.iter().filter(|m| m.authorized || !opts.authorized_only) {
+        let mut update = MemberUpdate::from(member);
+        let issues = &mut report.issues;
+        if !caps.member_names {
+            if let Some(name) = update.name.take().filter(|n| !n.is_empty()) {
+                issues.push(member_issue(&member.id, "name", format!("成员名称「{}」未迁移：目标不支持成员名称", name)));
+            }
+        }
+        if !caps.descriptions && update.description.take().is_some_and(|d| !d.is_empty()) {
+            issues.push(member_issue(&member.id, "description", "成员描述未迁移：目标不支持描述".into()));
+        }
+        if !member.tags.is_empty() && update.tags.is_some() && !rules_carried(&network, target) {
+            update.tags = None;
+            issues.push(member_issue(&member.id, "tags", "标签未迁移：规则未能迁移，标签 ID 在目标上没有意义".into()));
+        }
+
+        if let Err(e) = target.update_member(&report.target_network_id, &member.id, &update).await {
+            return Err(Error::Incomplete { report: Box::new(report), source: Box::new(e) });
+        }
+        report.members.push(member.id.clone());
+    }
+
+    Ok(report)
+}
+
+/// 目标能否获得可生效的规则
+fn rules_carried(network: &NetworkInfo, target: &dyn NetworkController) -> bool {
+    let caps = target.capabilities();
+    let has_source = network.rules_source.as_deref().is_some_and(|s| !s.trim().is_empty());
+    if caps.compiled_rules {
+        !network.rules.is_empty()
+    } else {
+        has_source
+    }
+}
+
+fn network_update(network: &NetworkInfo, target: &dyn NetworkController, issues: &mut Vec<MigrationIssue>) -> NetworkUpdate {
+    let caps = target.capabilities();
+    let mut update = NetworkUpdate::from(network);
+
+    if !caps.descriptions && update.description.take().is_some_and(|d| !d.is_empty()) {
+        issues.push(network_issue("description", "网络描述未迁移：目标不支持描述"));
+    }
+    if network.sso_enabled && !caps.sso {
+        issues.push(network_issue("sso", "源网络启用了 SSO，目标不支持 SSO，成员仅依靠授权接入"));
+    }
+
+    let has_source = network.rules_source.as_deref().is_some_and(|s| !s.trim().is_empty());
+    if !caps.rules_source {
+        update.rules_source = None;
+    }
+    if caps.compiled_rules {
+        if network.rules.is_empty() {
+            update.rules = None;
+            if has_source {
+                issues.push(network_issue("rules", "源网络只有规则源而没有编译后的规则，目标控制器将使用默认规则"));
+            }
+        }
+    } else {
+        update.rules = None;
+        if !has_source && !network.rules.is_empty() {
+            issues.push(network_issue("rules", "源网络只有编译后的规则，无法还原规则源，目标将使用默认规则"));
+        }
+    }
+
+    update
+}
+
+fn network_issue(field: &str, message: &str) -> MigrationIssue {
+    MigrationIssue { member_id: None, field: field.to_string(), message: message.to_string() }
+}
+
+fn member_issue(member_id: &str, field: &str, message: String) -> MigrationIssue {
+    MigrationIssue { member_id: Some(member_id.to_string()), field: field.to_string(), message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::{Backend, Capabilities, MemberInfo};
+    use crate::central;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    const CENTRAL: Capabilities =
+        Capabilities { member_names: true, descriptions: true, rules_source: true, compiled_rules: false, sso: true };
+    const SELF_HOSTED: Capabilities =
+        Capabilities { member_names: false, descriptions: false, rules_source: false, compiled_rules: true, sso: false };
+
+    /// 内存中的控制器，记录写入内容
+    struct Fake {
+        caps: Capabilities,
+        networks: Mutex<Vec<NetworkInfo>>,
+        members: Mutex<Vec<MemberInfo>>,
+        writes: Mutex<Vec<(String, NetworkUpdate)>>,
+        member_writes: Mutex<Vec<(String, MemberUpdate)>>,
+        /// 写入该成员时失败
+        fail_member: Option<String>,
+    }
+
+    impl Fake {
+        fn new(caps: Capabilities, networks: Vec<NetworkInfo>, members: Vec<MemberInfo>) -> Self {
+            Self {
+                caps,
+                networks: Mutex::new(networks),
+                members: Mutex::new(members),
+                writes: Mutex::default(),
+                member_writes: Mutex::default(),
+                fail_member: None,
+            }
+        }
+
+        fn network_write(&self) -> NetworkUpdate {
+            self.writes.lock().unwrap().last().unwrap().1.clone()
+        }
+
+        fn member_write(&self, id: &str) -> MemberUpdate {
+            self.member_writes.lock().unwrap().iter().find(|(m, _)| m == id).unwrap().1.clone()
+        }
+    }
+
+    fn not_found() -> Error {
+        Error::Central(central::Error::Api { status: 404, message: "not found".into() })
+    }
+
+    #[async_trait::async_trait]
+    impl NetworkController for Fake {
+        fn backend(&self) -> Backend {
+            if self.caps.sso { Backend::Central } else { Backend::SelfHosted }
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            self.caps
+        }
+
+        async fn list_networks(&self) -> Result<Vec<NetworkInfo>, Error> {
+            Ok(self.networks.lock().unwrap().clone())
+        }
+
+        async fn get_network(&self, network_id: &str) -> Result<NetworkInfo, Error> {
+            self.networks.lock().unwrap().iter().find(|n| n.id == network_id).cloned().ok_or_else(not_found)
+        }
+
+        async fn create_network(&self, update: &NetworkUpdate) -> Result<NetworkInfo, Error> {
+            let mut network = network("deadbeef00c13c86", update.name.as_deref().unwrap_or_default());
+            network.id = format!("deadbeef00{:06x}", self.networks.lock().unwrap().len());
+            self.writes.lock().unwrap().push((network.id.clone(), update.clone()));
+            self.networks.lock().unwrap().push(network.clone());
+            Ok(network)
+        }
+
+        async fn update_network(&self, network_id: &str, update: &NetworkUpdate) -> Result<NetworkInfo, Error> {
+            self.writes.lock().unwrap().push((network_id.to_string(), update.clone()));
+            self.get_network(network_id).await
+        }
+
+        async fn delete_network(&self, _network_id: &str) -> Result<(), Error> {
+            unimplemented!()
+        }
+
+        async fn list_members(&self, network_id: &str) -> Result<Vec<MemberInfo>, Error> {
+            Ok(self.members.lock().unwrap().iter().filter(|m| m.network_id == network_id).cloned().collect())
+        }
+
+        async fn get_member(&self, _network_id: &str, _member_id: &str) -> Result<MemberInfo, Error> {
+            unimplemented!()
+        }
+
+        async fn update_member(&self, network_id: &str, member_id: &str, update: &MemberUpdate) -> Result<MemberInfo, Error> {
+            if self.fail_member.as_deref() == Some(member_id) {
+                return Err(Error::Central(central::Error::Api { status: 500, message: "boom".into() }));
+            }
+            self.member_writes.lock().unwrap().push((member_id.to_string(), update.clone()));
+            Ok(member(network_id, member_id, None))
+        }
+
+        async fn delete_member(&self, _network_id: &str, _member_id: &str) -> Result<(), Error> {
+            unimplemented!()
+        }
+    }
+
+    fn network(id: &str, name: &str) -> NetworkInfo {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": name,
+            "private": true,
+            "enableBroadcast": true,
+            "multicastLimit": 32,
+            "routes": [{ "target": "10.147.20.0/24" }],
+            "ipAssignmentPools": [],
+            "rules": [],
+            "ssoEnabled": false,
+            "creationTime": 0,
+        }))
+        .unwrap()
+    }
+
+    fn member(network_id: &str, id: &str, name: Option<&str>) -> MemberInfo {
+        serde_json::from_value(json!({
+            "id": id,
+            "networkId": network_id,
+            "name": name,
+            "authorized": true,
+            "activeBridge": false,
+            "noAutoAssignIps": false,
+            "ipAssignments": ["10.147.20.10"],
+            "tags": [[1, 100]],
+            "creationTime": 0,
+        }))
+        .unwrap()
+    }
+
+    /// Central 上带规则源、描述与 SSO 的网络
+    fn central_source() -> Fake {
+        let mut source = network("8056c2e21c000001", "homelab");
+        source.description = Some("家庭实验网络".into());
+        source.rules_source = Some("tag department id 1;\naccept;".into());
+        source.sso_enabled = true;
+        let members = vec![
+            member("8056c2e21c000001", "abcdef0123", Some("laptop")),
+            member("8056c2e21c000001", "1111111111", None),
+        ];
+        Fake::new(CENTRAL, vec![source], members)
+    }
+
+    fn fields(issues: &[MigrationIssue]) -> Vec<(Option<&str>, &str)> {
+        issues.iter().map(|i| (i.member_id.as_deref(), i.field.as_str())).collect()
+    }
+
+    #[tokio::test]
+    async fn central_to_self_hosted_strips_unsupported_fields() {
+        let source = central_source();
+        let target = Fake::new(SELF_HOSTED, Vec::new(), Vec::new());
+        let report = migrate_network(&source, &target, "8056c2e21c000001", &MigrateOptions::default()).await.unwrap();
+
+        assert!(report.created && report.network_id_changed());
+        assert_eq!(report.members, ["abcdef0123", "1111111111"]);
+        assert_eq!(
+            fields(&report.issues),
+            [
+                (None, "description"),
+                (None, "sso"),
+                (None, "rules"),
+                (Some("abcdef0123"), "name"),
+                (Some("abcdef0123"), "tags"),
+                (Some("1111111111"), "tags"),
+            ]
+        );
+        let update = target.network_write();
+        assert!(update.description.is_none() && update.rules_source.is_none() && update.rules.is_none());
+        let laptop = target.member_write("abcdef0123");
+        assert!(laptop.name.is_none() && laptop.tags.is_none());
+        assert_eq!(laptop.ip_assignments.as_deref(), Some(&["10.147.20.10".to_string()][..]));
+    }
+
+    #[tokio::test]
+    async fn compiled_rules_carry_tags_to_self_hosted() {
+        let source = central_source();
+        source.networks.lock().unwrap()[0].rules = vec![json!({ "type": "ACTION_ACCEPT" })];
+        let target = Fake::new(SELF_HOSTED, Vec::new(), Vec::new());
+        let report = migrate_network(&source, &target, "8056c2e21c000001", &MigrateOptions::default()).await.unwrap();
+
+        assert!(!fields(&report.issues).contains(&(None, "rules")));
+        assert!(report.issues.iter().all(|i| i.field != "tags"));
+        assert_eq!(target.network_write().rules.map(|r| r.len()), Some(1));
+        assert_eq!(target.member_write("abcdef0123").tags, Some(vec![(1, 100)]));
+    }
+
+    #[tokio::test]
+    async fn self_hosted_to_central_needs_a_rules_source() {
+        let mut network = network("deadbeef00c13c86", "homelab");
+        network.rules = vec![json!({ "type": "ACTION_ACCEPT" })];
+        let source = Fake::new(SELF_HOSTED, vec![network], vec![member("deadbeef00c13c86", "abcdef0123", None)]);
+        let target = Fake::new(CENTRAL, Vec::new(), Vec::new());
+        let report = migrate_network(&source, &target, "deadbeef00c13c86", &MigrateOptions::default()).await.unwrap();
+
+        assert_eq!(fields(&report.issues), [(None, "rules"), (Some("abcdef0123"), "tags")]);
+        let update = target.network_write();
+        assert!(update.rules.is_none() && update.rules_source.is_none());
+    }
+
+    #[tokio::test]
+    async fn existing_network_with_the_same_name_is_updated() {
+        let source = central_source();
+        let target = Fake::new(CENTRAL, vec![network("8056c2e21c0000aa", "homelab")], Vec::new());
+        let report = migrate_network(&source, &target, "8056c2e21c000001", &MigrateOptions::default()).await.unwrap();
+
+        assert!(!report.created);
+        assert_eq!(report.target_network_id, "8056c2e21c0000aa");
+        assert!(report.issues.is_empty(), "{:?}", fields(&report.issues));
+        assert_eq!(target.member_write("abcdef0123").name.as_deref(), Some("laptop"));
+    }
+
+    #[tokio::test]
+    async fn ambiguous_target_is_an_error_before_any_write() {
+        let source = central_source();
+        let target = Fake::new(CENTRAL, vec![network("8056c2e21c0000aa", "homelab"), network("8056c2e21c0000bb", "homelab")], Vec::new());
+        let err = migrate_network(&source, &target, "8056c2e21c000001", &MigrateOptions::default()).await.unwrap_err();
+
+        assert!(matches!(err, Error::AmbiguousNetwork { ids, .. } if ids == ["8056c2e21c0000aa", "8056c2e21c0000bb"]));
+        assert!(target.writes.lock().unwrap().is_empty());
+
+        let opts = MigrateOptions { target_network_id: Some("8056c2e21c0000bb".into()), ..Default::default() };
+        let report = migrate_network(&source, &target, "8056c2e21c000001", &opts).await.unwrap();
+        assert_eq!(report.target_network_id, "8056c2e21c0000bb");
+    }
+
+    #[tokio::test]
+    async fn member_failure_returns_the_partial_report() {
+        let source = central_source();
+        let mut target = Fake::new(SELF_HOSTED, Vec::new(), Vec::new());
+        target.fail_member = Some("1111111111".into());
+        let err = migrate_network(&source, &target, "8056c2e21c000001", &MigrateOptions::default()).await.unwrap_err();
+
+        let Error::Incomplete { report, source } = err else { panic!("expected Incomplete, got {err}") };
+        assert!(report.created);
+        assert_eq!(report.target_network_id, target.networks.lock().unwrap()[0].id);
+        assert_eq!(report.members, ["abcdef0123"]);
+        assert!(fields(&report.issues).contains(&(Some("abcdef0123"), "name")));
+        assert!(matches!(*source, Error::Central(central::Error::Api { status: 500, .. })));
+    }
+
+    #[tokio::test]
+    async fn authorized_only_skips_unauthorized_members() {
+        let source = central_source();
+        source.members.lock().unwrap()[1].authorized = false;
+        let target = Fake::new(CENTRAL, Vec::new(), Vec::new());
+        let opts = MigrateOptions { authorized_only: true, ..Default::default() };
+        let report = migrate_network(&source, &target, "8056c2e21c000001", &opts).await.unwrap();
+        assert_eq!(report.members, ["abcdef0123"]);
+    }
+}
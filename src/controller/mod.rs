@@ -0,0 +1,106 @@
+//! Central 与自托管控制器的统一抽象
+//!
+//! `NetworkController` 以同一套网络/成员模型覆盖 `central::Central` 与
+//! `client::ControllerService`，上层工具无需区分后端。
+
+mod types;
+mod central;
+mod local;
+mod migrate;
+
+pub use types::*;
+pub use migrate::{migrate_network, MigrateOptions, MigrationIssue, MigrationReport};
+
+use crate::{central as central_api, client};
+use serde::Serialize;
+
+/// 控制器后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    Central,
+    SelfHosted,
+}
+
+/// 后端支持的能力
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Capabilities {
+    /// 成员名称
+    pub member_names: bool,
+    /// 网络与成员描述
+    pub descriptions: bool,
+    /// 存储规则源
+    pub rules_source: bool,
+    /// 直接写入编译后的规则
+    pub compiled_rules: bool,
+    /// SSO 认证
+    pub sso: bool,
+}
+
+/// 网络控制器
+///
+/// 后端不支持的字段（见 [`NetworkController::capabilities`]）在写入时会被忽略。
+#[async_trait::async_trait]
+pub trait NetworkController: Send + Sync {
+    /// 后端类型
+    fn backend(&self) -> Backend;
+
+    /// 后端能力
+    fn capabilities(&self) -> Capabilities;
+
+    /// 列出所有网络
+    async fn list_networks(&self) -> Result<Vec<NetworkInfo>, Error>;
+
+    /// 获取网络详情
+    async fn get_network(&self, network_id: &str) -> Result<NetworkInfo, Error>;
+
+    /// 创建网络（网络 ID 由后端分配）
+    async fn create_network(&self, update: &NetworkUpdate) -> Result<NetworkInfo, Error>;
+
+    /// 更新网络
+    async fn update_network(&self, network_id: &str, update: &NetworkUpdate) -> Result<NetworkInfo, Error>;
+
+    /// 删除网络
+    async fn delete_network(&self, network_id: &str) -> Result<(), Error>;
+
+    /// 列出网络成员
+    async fn list_members(&self, network_id: &str) -> Result<Vec<MemberInfo>, Error>;
+
+    /// 获取成员详情
+    async fn get_member(&self, network_id: &str, member_id: &str) -> Result<MemberInfo, Error>;
+
+    /// 更新成员（成员不存在时创建）
+    async fn update_member(&self, network_id: &str, member_id: &str, update: &MemberUpdate) -> Result<MemberInfo, Error>;
+
+    /// 删除成员
+    async fn delete_member(&self, network_id: &str, member_id: &str) -> Result<(), Error>;
+
+    /// 授权成员
+    async fn authorize(&self, network_id: &str, member_id: &str) -> Result<MemberInfo, Error> {
+        let update = MemberUpdate { authorized: Some(true), ..Default::default() };
+        self.update_member(network_id, member_id, &update).await
+    }
+
+    /// 取消授权
+    async fn deauthorize(&self, network_id: &str, member_id: &str) -> Result<MemberInfo, Error> {
+        let update = MemberUpdate { authorized: Some(false), ..Default::default() };
+        self.update_member(network_id, member_id, &update).await
+    }
+}
+
+/// 错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Central error: {0}")]
+    Central(#[from] central_api::Error),
+
+    #[error("Local error: {0}")]
+    Local(#[from] client::Error),
+
+    #[error("Multiple target networks named \"{name}\" ({}); set target_network_id", ids.join(", "))]
+    AmbiguousNetwork { name: String, ids: Vec<String> },
+
+    /// 目标网络已创建或更新后迁移中断，`report` 为已完成的部分
+    #[error("Migration to {} stopped after {} members: {source}", report.target_network_id, report.members.len())]
+    Incomplete { report: Box<MigrationReport>, source: Box<Error> },
+}
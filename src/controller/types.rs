@@ -0,0 +1,114 @@
+use crate::central::{AssignMode, Dns, IpAssignmentPool, Route};
+use serde::{Deserialize, Serialize};
+
+/// 与后端无关的网络模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInfo {
+    pub id: String,
+    pub name: String,
+    /// 仅 Central 支持
+    pub description: Option<String>,
+    pub private: bool,
+    pub enable_broadcast: bool,
+    pub mtu: Option<i32>,
+    pub multicast_limit: i32,
+    pub routes: Vec<Route>,
+    pub ip_assignment_pools: Vec<IpAssignmentPool>,
+    pub v4_assign_mode: Option<AssignMode>,
+    pub v6_assign_mode: Option<AssignMode>,
+    pub dns: Option<Dns>,
+    pub rules_source: Option<String>,
+    /// 编译后的流规则
+    pub rules: Vec<serde_json::Value>,
+    pub sso_enabled: bool,
+    pub creation_time: i64,
+}
+
+/// 与后端无关的成员模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberInfo {
+    /// 成员节点 ID（10 位十六进制）
+    pub id: String,
+    pub network_id: String,
+    /// 仅 Central 支持
+    pub name: Option<String>,
+    /// 仅 Central 支持
+    pub description: Option<String>,
+    pub authorized: bool,
+    pub active_bridge: bool,
+    pub no_auto_assign_ips: bool,
+    pub ip_assignments: Vec<String>,
+    pub tags: Vec<(i32, i32)>,
+    /// 最后在线时间（毫秒时间戳），仅 Central 支持
+    pub last_seen: Option<i64>,
+    pub creation_time: i64,
+}
+
+/// 网络创建/更新内容，`None` 表示不修改
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkUpdate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub private: Option<bool>,
+    pub enable_broadcast: Option<bool>,
+    pub mtu: Option<i32>,
+    pub multicast_limit: Option<i32>,
+    pub routes: Option<Vec<Route>>,
+    pub ip_assignment_pools: Option<Vec<IpAssignmentPool>>,
+    pub v4_assign_mode: Option<AssignMode>,
+    pub v6_assign_mode: Option<AssignMode>,
+    pub dns: Option<Dns>,
+    pub rules_source: Option<String>,
+    /// 编译后的流规则，仅自托管控制器使用（Central 由规则源编译）
+    pub rules: Option<Vec<serde_json::Value>>,
+}
+
+/// 成员更新内容，`None` 表示不修改
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberUpdate {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub authorized: Option<bool>,
+    pub active_bridge: Option<bool>,
+    pub no_auto_assign_ips: Option<bool>,
+    pub ip_assignments: Option<Vec<String>>,
+    pub tags: Option<Vec<(i32, i32)>>,
+}
+
+impl From<&NetworkInfo> for NetworkUpdate {
+    fn from(n: &NetworkInfo) -> Self {
+        Self {
+            name: Some(n.name.clone()),
+            description: n.description.clone(),
+            private: Some(n.private),
+            enable_broadcast: Some(n.enable_broadcast),
+            mtu: n.mtu,
+            multicast_limit: Some(n.multicast_limit),
+            routes: Some(n.routes.clone()),
+            ip_assignment_pools: Some(n.ip_assignment_pools.clone()),
+            v4_assign_mode: n.v4_assign_mode.clone(),
+            v6_assign_mode: n.v6_assign_mode.clone(),
+            dns: n.dns.clone(),
+            rules_source: n.rules_source.clone(),
+            rules: Some(n.rules.clone()),
+        }
+    }
+}
+
+impl From<&MemberInfo> for MemberUpdate {
+    fn from(m: &MemberInfo) -> Self {
+        Self {
+            name: m.name.clone(),
+            description: m.description.clone(),
+            authorized: Some(m.authorized),
+            active_bridge: Some(m.active_bridge),
+            no_auto_assign_ips: Some(m.no_auto_assign_ips),
+            ip_assignments: Some(m.ip_assignments.clone()),
+            tags: Some(m.tags.clone()),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! 演练（dry-run）模式：写请求不发送，改为记录读取到的当前状态与预期变更
+//!
+//! 写方法照常返回 `Ok`，返回值按预期状态合成（缺少的必填字段取零值），因此包含多个写请求的流程
+//! （如状态文件 apply、备份恢复、网络迁移）可以完整演练；所有写请求按发送顺序记录在客户端上，
+//! 通过 `planned_writes` / `take_planned_writes` 取出。
+
+use crate::state::FieldChange;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// 演练模式下新建资源的占位 ID（真实 ID 由服务端生成）；路径中含占位 ID 的写请求不读取当前状态
+pub const PLACEHOLDER_ID: &str = "<new>";
+
+/// 演练模式下未发送的写请求
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PlannedWrite {
+    /// HTTP 方法（POST / DELETE）
+    pub method: String,
+    /// API 路径
+    pub path: String,
+    /// 将要发送的请求体
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<Value>,
+    /// 当前状态，资源尚不存在时为空
+    pub before: Option<Value>,
+    /// 写入后的预期状态，删除时为空
+    pub after: Option<Value>,
+    /// 将发生变化的字段（嵌套字段以 `.` 连接）
+    pub changes: Vec<FieldChange>,
+}
+
+impl PlannedWrite {
+    pub(crate) fn new(method: &reqwest::Method, path: &str, request: Option<Value>, before: Option<Value>) -> Self {
+        let (after, changes) = if *method == reqwest::Method::DELETE {
+            (None, Vec::new())
+        } else {
+            let mut after = before.clone().unwrap_or_else(|| Value::Object(Map::new()));
+            let mut changes = Vec::new();
+            if let Some(request) = &request {
+                merge(&mut after, request, "", &mut changes);
+            }
+            (Some(after), changes)
+        };
+        Self { method: method.to_string(), path: path.to_string(), request, before, after, changes }
+    }
+
+    /// 新建资源的预期状态使用占位 ID，后续写请求（如为新网络添加成员）可据此构造路径
+    pub(crate) fn with_placeholder_id(mut self) -> Self {
+        if let Some(Value::Object(after)) = &mut self.after {
+            after.entry("id").or_insert_with(|| Value::String(PLACEHOLDER_ID.to_string()));
+        }
+        self
+    }
+
+    /// 删除资源
+    pub fn is_delete(&self) -> bool {
+        self.method == reqwest::Method::DELETE.as_str()
+    }
+
+    /// 创建资源（写入前不存在）
+    pub fn is_create(&self) -> bool {
+        !self.is_delete() && self.before.is_none()
+    }
+}
+
+/// 演练模式下记录的写请求，客户端的克隆共享同一份记录
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PlannedWrites(Arc<Mutex<Vec<PlannedWrite>>>);
+
+impl PlannedWrites {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<PlannedWrite>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn push(&self, write: PlannedWrite) {
+        self.lock().push(write);
+    }
+
+    pub(crate) fn extend(&self, writes: impl IntoIterator<Item = PlannedWrite>) {
+        self.lock().extend(writes);
+    }
+
+    pub(crate) fn list(&self) -> Vec<PlannedWrite> {
+        self.lock().clone()
+    }
+
+    pub(crate) fn take(&self) -> Vec<PlannedWrite> {
+        std::mem::take(&mut *self.lock())
+    }
+}
+
+/// 按写入后的预期状态合成写方法的返回值；预期状态中缺少的必填字段按 schema 取零值
+pub(crate) fn synthesize<T: DeserializeOwned + JsonSchema>(write: &PlannedWrite) -> Result<T, serde_json::Error> {
+    let schema = schemars::schema_for!(T);
+    let root = schema.as_value();
+    let defs = root.get("$defs").unwrap_or(&Value::Null);
+    let mut value = write.after.clone().unwrap_or_else(|| Value::Object(Map::new()));
+    fill_required(&mut value, root, defs);
+    serde_json::from_value(value)
+}
+
+/// 展开 `$ref`（只支持本文档内的 `#/$defs/...`）
+fn resolve<'a>(schema: &'a Value, defs: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix("#/$defs/")) {
+        Some(name) => defs.get(name).map_or(schema, |s| resolve(s, defs)),
+        None => schema,
+    }
+}
+
+/// `anyOf` / `oneOf` 中的非 null 分支
+fn variants(schema: &Value) -> impl Iterator<Item = &Value> {
+    ["anyOf", "oneOf"]
+        .into_iter()
+        .filter_map(|key| schema.get(key).and_then(Value::as_array))
+        .flatten()
+        .filter(|s| s.get("type").and_then(Value::as_str) != Some("null"))
+}
+
+/// 为对象补齐缺少的必填字段，并递归处理已有的对象与数组
+fn fill_required(value: &mut Value, schema: &Value, defs: &Value) {
+    let schema = resolve(schema, defs);
+    match value {
+        Value::Object(object) => {
+            if let Some(variant) = variants(schema).map(|s| resolve(s, defs)).find(|s| s.get("properties").is_some()) {
+                return fill_required(value, variant, defs);
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for name in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    let property = properties.and_then(|p| p.get(name)).unwrap_or(&Value::Null);
+                    object.insert(name.to_string(), zero_value(property, defs));
+                }
+            }
+            for (name, field) in object.iter_mut() {
+                if let Some(property) = properties.and_then(|p| p.get(name)) {
+                    fill_required(field, property, defs);
+                }
+            }
+        }
+        Value::Array(items) => {
+            let item = variants(schema).next().map(|s| resolve(s, defs)).unwrap_or(schema);
+            if let Some(item) = item.get("items") {
+                for value in items {
+                    fill_required(value, item, defs);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 字段的零值：可为 null 时为 null，否则按类型取空值
+fn zero_value(schema: &Value, defs: &Value) -> Value {
+    let schema = resolve(schema, defs);
+    if let Some(value) = schema.get("const").or_else(|| schema.get("enum").and_then(|e| e.get(0))) {
+        return value.clone();
+    }
+    if let Some(any) = schema.get("anyOf").or_else(|| schema.get("oneOf")).and_then(Value::as_array) {
+        return match variants(schema).next() {
+            Some(_) if any.len() > variants(schema).count() => Value::Null,
+            Some(variant) => zero_value(variant, defs),
+            None => Value::Null,
+        };
+    }
+    let kind = match schema.get("type") {
+        Some(Value::Array(types)) if types.iter().any(|t| t == "null") => return Value::Null,
+        Some(Value::Array(types)) => types.first().and_then(Value::as_str),
+        Some(kind) => kind.as_str(),
+        None => None,
+    };
+    match kind {
+        Some("string") => Value::String(String::new()),
+        Some("integer" | "number") => Value::from(0),
+        Some("boolean") => Value::Bool(false),
+        Some("array") => Value::Array(Vec::new()),
+        Some("object") => {
+            let mut value = Value::Object(Map::new());
+            fill_required(&mut value, schema, defs);
+            value
+        }
+        _ => Value::Null,
+    }
+}
+
+/// 将请求体合并到当前状态（对象逐字段合并，其余整体替换），并记录变化的字段
+fn merge(target: &mut Value, patch: &Value, prefix: &str, changes: &mut Vec<FieldChange>) {
+    let (Value::Object(target), Value::Object(patch)) = (&mut *target, patch) else {
+        if target != patch {
+            changes.push(FieldChange { field: prefix.to_string(), before: target.clone(), after: patch.clone() });
+            *target = patch.clone();
+        }
+        return;
+    };
+    for (key, value) in patch {
+        let field = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match target.get_mut(key) {
+            Some(current) if current.is_object() && value.is_object() => merge(current, value, &field, changes),
+            Some(current) => {
+                if current != value {
+                    changes.push(FieldChange { field, before: current.clone(), after: value.clone() });
+                    *current = value.clone();
+                }
+            }
+            None if value.is_null() => {}
+            None if value.is_object() => {
+                let mut current = Value::Object(Map::new());
+                merge(&mut current, value, &field, changes);
+                target.insert(key.clone(), current);
+            }
+            None => {
+                changes.push(FieldChange { field, before: Value::Null, after: value.clone() });
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl fmt::Display for PlannedWrite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} (dry run, not sent)", self.method, self.path)?;
+        if !self.is_delete() && self.changes.is_empty() {
+            write!(f, "\n    no changes")?;
+        }
+        for c in &self.changes {
+            if self.is_create() {
+                write!(f, "\n    + {} = {}", c.field, c.after)?;
+            } else {
+                write!(f, "\n    ~ {}: {} -> {}", c.field, c.before, c.after)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{central, client};
+    use serde_json::json;
+
+    #[test]
+    fn synthesizes_created_network_with_placeholder_id() {
+        let request = json!({ "config": { "name": "lab", "private": true } });
+        let write = PlannedWrite::new(&reqwest::Method::POST, "/network", Some(request), None).with_placeholder_id();
+        assert!(write.is_create());
+        let network: central::Network = synthesize(&write).unwrap();
+        assert_eq!(network.id, PLACEHOLDER_ID);
+        assert_eq!(network.config.unwrap().name, "lab");
+    }
+
+    #[test]
+    fn synthesizes_updated_member_from_before() {
+        let before = json!({ "nodeId": "abcdef0123", "name": "laptop", "config": { "authorized": false } });
+        let request = json!({ "config": { "authorized": true } });
+        let write = PlannedWrite::new(&reqwest::Method::POST, "/network/1/member/abcdef0123", Some(request), Some(before));
+        assert_eq!(write.changes.len(), 1);
+        assert_eq!(write.changes[0].field, "config.authorized");
+        let member: central::Member = synthesize(&write).unwrap();
+        assert_eq!(member.node_id, "abcdef0123");
+        assert!(member.config.unwrap().authorized);
+    }
+
+    #[test]
+    fn synthesizes_joined_network_without_state() {
+        let write = PlannedWrite::new(&reqwest::Method::POST, "/network/8056c2e21c000001", Some(json!({})), None);
+        let network: client::Network = synthesize(&write).unwrap();
+        assert!(network.assigned_addresses.is_empty());
+    }
+}
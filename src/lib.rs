@@ -1,13 +1,18 @@
 //! ZeroTier SDK for Rust
 //!
-//! 本 SDK 包含两个子模块：
+//! 本 SDK 包含以下子模块：
 //! - `client`: 本地 Service API（localhost:9993）
 //! - `central`: 云端 Central API（api.zerotier.com）
+//! - `setup`: 基于 CIDR 的网络快速配置
+//! - `state`: 声明式期望状态（plan / apply）
+//! - `backup`: Central 网络与成员的备份与恢复
+//! - `controller`: Central 与自托管控制器的统一抽象
+//! - `dry_run`: 演练模式，写请求只返回预期变更而不发送
 //!
 //! # 快速开始
 //!
 //! ```rust,no_run
-//! use zerotier_sdk::{Client, Central};
+//! use zerotier_sdk_rust_mcp::{Client, Central};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,8 +30,15 @@
 pub mod client;
 pub mod central;
 pub mod mcp;
+pub mod setup;
+pub mod state;
+pub mod backup;
+pub mod controller;
+pub mod dry_run;
 
 // 重导出主要类型
 pub use client::Client;
 pub use central::Central;
 pub use mcp::McpServer;
+pub use setup::NetworkSetup;
+pub use controller::NetworkController;
@@ -0,0 +1,117 @@
+//! 工具访问控制：只读模式与允许/禁止列表
+
+use super::error::{ErrorKind, ToolError};
+use super::i18n::tr;
+use super::server::McpServer;
+use rmcp::model::Tool;
+use std::collections::BTreeSet;
+
+/// 工具访问策略
+///
+/// 名称支持以 `*` 结尾的前缀匹配，如 `zt_central_*`。
+#[derive(Debug, Clone, Default)]
+pub struct AccessPolicy {
+    read_only: bool,
+    allow: Option<BTreeSet<String>>,
+    deny: BTreeSet<String>,
+}
+
+impl AccessPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 只读模式：仅允许带 `readOnlyHint` 的工具
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// 加入允许列表；一旦设置，未列出的工具均不可用
+    pub fn allow_tool(mut self, pattern: impl Into<String>) -> Self {
+        self.allow.get_or_insert_with(BTreeSet::new).insert(pattern.into());
+        self
+    }
+
+    /// 加入禁止列表，优先于允许列表
+    pub fn deny_tool(mut self, pattern: impl Into<String>) -> Self {
+        self.deny.insert(pattern.into());
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// 检查工具是否可用，不可用时返回原因
+    ///
+    /// `central_read_only` 为 Central Token 自身处于只读模式，仅影响 `zt_central_*` 写操作。
+    pub(super) fn check(&self, tool: &Tool, central_read_only: bool) -> Result<(), String> {
+        let name = tool.name.as_ref();
+        if let Some(pattern) = self.deny.iter().find(|p| matches(p, name)) {
+            return Err(tr!("工具 {} 已被禁用：匹配禁止列表中的 \"{}\"", name, pattern));
+        }
+        if let Some(allow) = &self.allow {
+            if !allow.iter().any(|p| matches(p, name)) {
+                return Err(tr!("工具 {} 已被禁用：不在允许列表中", name));
+            }
+        }
+        if is_mutating(tool) {
+            if self.read_only {
+                return Err(tr!("工具 {} 已被禁用：服务处于只读模式", name));
+            }
+            if central_read_only && name.starts_with("zt_central_") {
+                return Err(tr!(
+                    "工具 {} 已被禁用：Central API Token 处于只读模式（read_only_mode），不允许修改云端数据",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 未标注 `readOnlyHint = true` 的工具均视为写操作
+pub(super) fn is_mutating(tool: &Tool) -> bool {
+    tool.annotations.as_ref().and_then(|a| a.read_only_hint) != Some(true)
+}
+
+fn matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+impl McpServer {
+    /// Central Token 是否处于只读模式；查询失败时视为否且不缓存
+    async fn central_read_only(&self) -> bool {
+        let Some(central) = &self.central_client else {
+            return false;
+        };
+        let result = self
+            .central_read_only
+            .get_or_try_init(|| async { central.status().await.map(|s| s.read_only_mode) })
+            .await;
+        result.copied().unwrap_or(false)
+    }
+
+    /// 调用前检查访问策略
+    pub(super) async fn check_tool(&self, tool: &Tool) -> Result<(), ToolError> {
+        let central_read_only = is_mutating(tool) && tool.name.starts_with("zt_central_") && self.central_read_only().await;
+        self.access
+            .check(tool, central_read_only)
+            .map_err(|message| ToolError::new(ErrorKind::Forbidden, message))
+    }
+
+    /// 当前策略下可用的工具
+    pub(super) async fn visible_tools(&self) -> Vec<Tool> {
+        let mut tools = Vec::new();
+        for tool in self.tool_router.list_all() {
+            if self.check_tool(&tool).await.is_ok() {
+                tools.push(tool);
+            }
+        }
+        tools
+    }
+}
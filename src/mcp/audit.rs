@@ -0,0 +1,291 @@
+//! 写操作审计日志
+//!
+//! 每次写操作（含被策略拒绝的调用）追加一行 JSON 记录：时间、工具、参数、目标网络/成员（批量操作为逐个成员）、
+//! 执行前后快照、结果以及 MCP 会话中的客户端信息。文件超过大小上限时按
+//! `audit.jsonl` → `audit.jsonl.1` → `audit.jsonl.2` … 轮转。
+
+use super::i18n::tr;
+use super::profile::profile_arg;
+use super::server::{str_arg, McpServer};
+use axum::http::{header, request::Parts};
+use rmcp::{
+    model::{CallToolResult, JsonObject},
+    service::RequestContext,
+    ErrorData, RoleServer,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 审计日志文件
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// 创建审计日志，默认单个文件 10 MiB、保留 5 个轮转文件
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), max_size: 10 * 1024 * 1024, max_files: 5, lock: Mutex::new(()) }
+    }
+
+    /// 单个文件大小上限（字节）
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// 保留的轮转文件数，0 表示超限时直接清空
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = count;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 追加一条记录
+    pub(super) fn append(&self, record: &AuditRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&line)
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+        let _ = fs::remove_file(self.rotated(self.max_files));
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+}
+
+/// 审计结果
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Outcome {
+    Success,
+    Error,
+    /// 被访问策略或网络策略拒绝，未执行
+    Denied,
+}
+
+/// MCP 会话中的客户端信息
+#[derive(Debug, Default, Serialize)]
+pub(super) struct ClientInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol_version: Option<String>,
+    /// HTTP 传输时的会话 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    fn from_context(context: &RequestContext<RoleServer>) -> Self {
+        let mut info = Self::default();
+        if let Some(peer) = context.peer.peer_info() {
+            info.name = Some(peer.client_info.name.clone());
+            info.version = Some(peer.client_info.version.clone());
+            info.protocol_version = Some(peer.protocol_version.to_string());
+        }
+        if let Some(parts) = context.extensions.get::<Parts>() {
+            let header = |name: &str| parts.headers.get(name).and_then(|v| v.to_str().ok()).map(String::from);
+            info.session_id = header("mcp-session-id");
+            info.user_agent = header(header::USER_AGENT.as_str());
+        }
+        info
+    }
+}
+
+/// 批量操作涉及的成员
+#[derive(Debug, Serialize)]
+pub(super) struct AuditMember {
+    member_id: String,
+    name: String,
+    ok: bool,
+}
+
+impl AuditMember {
+    /// 从批量操作结果（`BulkReport`）的 `results` 中提取
+    fn from_report(report: &Value) -> Vec<Self> {
+        let results = report.get("results").and_then(Value::as_array);
+        results
+            .into_iter()
+            .flatten()
+            .map(|r| Self {
+                member_id: r.get("member_id").and_then(Value::as_str).unwrap_or_default().to_string(),
+                name: r.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+                ok: r.get("ok").and_then(Value::as_bool).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// 审计记录（JSONL 中的一行）
+#[derive(Debug, Serialize)]
+pub(super) struct AuditRecord {
+    /// Unix 毫秒时间戳
+    timestamp: i64,
+    tool: String,
+    /// 调用指定的 profile，未指定时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+    arguments: JsonObject,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    member_id: Option<String>,
+    before: Option<Value>,
+    after: Option<Value>,
+    /// 批量操作逐个处理的成员（ID、执行前的名称与是否成功）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    members: Vec<AuditMember>,
+    outcome: Outcome,
+    /// 失败或拒绝的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Value>,
+    client: ClientInfo,
+    duration_ms: u64,
+}
+
+impl AuditRecord {
+    pub(super) fn new(tool: &str, arguments: Option<&JsonObject>, context: &RequestContext<RoleServer>) -> Self {
+        let mut arguments = arguments.cloned().unwrap_or_default();
+        // 确认令牌不具审计意义
+        arguments.remove("confirm_token");
+        Self {
+            timestamp: now_millis(),
+            tool: tool.to_string(),
+            profile: profile_arg(&arguments).map(String::from),
+            network_id: str_arg(&arguments, "network_id").map(String::from),
+            member_id: str_arg(&arguments, "member_id").map(String::from),
+            arguments,
+            before: None,
+            after: None,
+            members: Vec::new(),
+            outcome: Outcome::Success,
+            error: None,
+            client: ClientInfo::from_context(context),
+            duration_ms: 0,
+        }
+    }
+
+    pub(super) fn denied(mut self, error: Value) -> Self {
+        self.outcome = Outcome::Denied;
+        self.error = Some(error);
+        self
+    }
+
+    /// 记录执行结果与耗时
+    pub(super) fn finish(&mut self, result: &Result<CallToolResult, ErrorData>) {
+        self.duration_ms = (now_millis() - self.timestamp).max(0) as u64;
+        match result {
+            Ok(result) if result.is_error == Some(true) => {
+                self.outcome = Outcome::Error;
+                self.error = result.structured_content.as_ref().and_then(|c| c.get("error")).cloned();
+            }
+            Ok(result) => {
+                self.outcome = Outcome::Success;
+                if self.tool == "zt_central_bulk_members" {
+                    self.members = result.structured_content.as_ref().map(AuditMember::from_report).unwrap_or_default();
+                }
+            }
+            Err(e) => {
+                self.outcome = Outcome::Error;
+                self.error = serde_json::to_value(e).ok();
+            }
+        }
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+impl McpServer {
+    /// 写入审计记录；写入失败不影响工具结果，仅输出到 stderr
+    pub(super) fn write_audit(&self, record: &AuditRecord) {
+        if let Some(log) = &self.audit {
+            if let Err(e) = log.append(record) {
+                eprintln!("{}", tr!("写入审计日志 {} 失败: {}", log.path().display(), e));
+            }
+        }
+    }
+
+    /// 目标网络或成员的当前状态，按工具所属的 API 读取
+    pub(super) async fn audit_snapshot(&self, tool: &str, arguments: Option<&JsonObject>) -> Option<Value> {
+        let network_id = arguments.and_then(|a| str_arg(a, "network_id"))?;
+        let member_id = arguments.and_then(|a| str_arg(a, "member_id"));
+        if tool.starts_with("zt_central_") {
+            let networks = self.central_client.as_ref()?.networks();
+            match member_id {
+                Some(member_id) => serde_json::to_value(networks.members(network_id).get(member_id).await.ok()?).ok(),
+                None => serde_json::to_value(networks.get(network_id).await.ok()?).ok(),
+            }
+        } else if tool.starts_with("zt_controller_") {
+            let controller = self.local_client.controller();
+            match member_id {
+                Some(member_id) => serde_json::to_value(controller.get_member(network_id, member_id).await.ok()?).ok(),
+                None => serde_json::to_value(controller.get_network(network_id).await.ok()?).ok(),
+            }
+        } else {
+            serde_json::to_value(self.local_client.networks().get(network_id).await.ok()?).ok()
+        }
+    }
+
+    /// 执行写操作并记录审计：执行前后各读取一次目标状态
+    ///
+    /// 参数中没有 `network_id`（如创建网络）时，以工具返回的结构化结果作为执行后快照。
+    pub(super) async fn audited<F>(&self, mut record: AuditRecord, call: F) -> Result<CallToolResult, ErrorData>
+    where
+        F: std::future::Future<Output = Result<CallToolResult, ErrorData>>,
+    {
+        let arguments = Some(&record.arguments);
+        record.before = self.audit_snapshot(&record.tool, arguments).await;
+        let result = call.await;
+        record.finish(&result);
+        record.after = match (&record.network_id, &result) {
+            (Some(_), _) => self.audit_snapshot(&record.tool, Some(&record.arguments)).await,
+            (None, Ok(result)) => result.structured_content.clone(),
+            (None, Err(_)) => None,
+        };
+        self.write_audit(&record);
+        result
+    }
+}
@@ -0,0 +1,831 @@
+//! Central 管理工具：网络的创建、更新配置、更新流规则与删除，成员的查询、标注、IP 指定、删除与批量操作
+
+use super::error::{failure, ToolError};
+use super::i18n::tr;
+use super::output::{schema, structured, MemberRef, NetworkRef, ToolResult};
+use super::server::{format_age, McpServer, MemberParam, NetworkIdParam};
+use crate::central::{
+    BulkAction, BulkReport, ClientVersion, CreateNetworkConfig, Dns, IpAssignmentPool, Member, MemberFilter, MemberQuery,
+    MemberQueryResult, MemberSort, Network, Route, UpdateMemberConfig, UpdateMemberRequest, UpdateNetworkRequest,
+};
+use crate::setup::{Cidr, NetworkSetup};
+use rmcp::{
+    handler::server::wrapper::Parameters,
+    model::ProgressNotificationParam,
+    schemars::{self, JsonSchema},
+    service::RequestContext,
+    tool, tool_router, RoleServer,
+};
+use serde::Deserialize;
+use regex::Regex;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+/// ZeroTier 允许的 MTU 范围
+const MTU_RANGE: std::ops::RangeInclusive<i32> = 1280..=10000;
+
+/// 批量操作的并发数范围
+const BULK_CONCURRENCY: std::ops::RangeInclusive<usize> = 1..=16;
+
+/// 成员查询默认与最多返回的数量
+const FIND_DEFAULT_LIMIT: usize = 50;
+const FIND_MAX_LIMIT: usize = 500;
+
+// ============================================
+// 工具参数定义
+// ============================================
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralCreateNetworkParam {
+    #[schemars(description = "网络名称")]
+    pub name: String,
+    #[schemars(description = "网段 CIDR（如 10.147.20.0/24），自动生成受管路由与 IP 分配池（可选）")]
+    pub cidr: Option<String>,
+    #[schemars(description = "是否为私有网络，默认 true（可选）")]
+    pub private: Option<bool>,
+    #[schemars(description = "网络描述（可选）")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RouteParam {
+    #[schemars(description = "目标网段 CIDR")]
+    pub target: String,
+    #[schemars(description = "网关地址，省略表示受管路由（可选）")]
+    pub via: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PoolParam {
+    #[schemars(description = "起始 IP")]
+    pub start: String,
+    #[schemars(description = "结束 IP")]
+    pub end: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralUpdateNetworkParam {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "新名称（可选）")]
+    pub name: Option<String>,
+    #[schemars(description = "新描述（可选）")]
+    pub description: Option<String>,
+    #[schemars(description = "是否为私有网络（可选）")]
+    pub private: Option<bool>,
+    #[schemars(description = "MTU，1280-10000（可选）")]
+    pub mtu: Option<i32>,
+    #[schemars(description = "路由列表，将替换现有路由（可选）")]
+    pub routes: Option<Vec<RouteParam>>,
+    #[schemars(description = "IP 分配池列表，将替换现有分配池（可选）")]
+    pub ip_assignment_pools: Option<Vec<PoolParam>>,
+    #[schemars(description = "DNS 搜索域，须与 dns_servers 同时提供；两者均为空时清除 DNS（可选）")]
+    pub dns_domain: Option<String>,
+    #[schemars(description = "DNS 服务器地址列表（可选）")]
+    pub dns_servers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralUpdateRulesParam {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "流规则源码（ZeroTier 规则语言），将替换现有规则")]
+    pub rules_source: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralUpdateMemberParam {
+    #[schemars(description = "网络 ID")]
+    pub network_id: String,
+    #[schemars(description = "成员 ID")]
+    pub member_id: String,
+    #[schemars(description = "新名称（可选）")]
+    pub name: Option<String>,
+    #[schemars(description = "新描述（可选）")]
+    pub description: Option<String>,
+    #[schemars(description = "是否允许作为以太网桥（可选）")]
+    pub active_bridge: Option<bool>,
+    #[schemars(description = "是否禁止从分配池自动分配 IP（可选）")]
+    pub no_auto_assign_ips: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralSetMemberIpsParam {
+    #[schemars(description = "网络 ID")]
+    pub network_id: String,
+    #[schemars(description = "成员 ID")]
+    pub member_id: String,
+    #[schemars(description = "IP 地址列表，将替换现有地址；空列表表示清除")]
+    pub ip_addresses: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TagParam {
+    #[schemars(description = "标签 ID")]
+    pub id: i32,
+    #[schemars(description = "标签值")]
+    pub value: i32,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralBulkMembersParam {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "操作：authorize、deauthorize、set_tags、delete 或 rename")]
+    pub action: String,
+    #[schemars(description = "只选择这些成员 ID（可选）")]
+    pub member_ids: Option<Vec<String>>,
+    #[schemars(description = "按授权状态筛选（可选）")]
+    pub authorized: Option<bool>,
+    #[schemars(description = "名称通配符，* 匹配任意字符，如 ci-*（可选）")]
+    pub name_pattern: Option<String>,
+    #[schemars(description = "只选择最近多少分钟内在线过的成员（可选）")]
+    pub seen_within_minutes: Option<u64>,
+    #[schemars(description = "set_tags 使用的标签列表，将替换现有标签")]
+    pub tags: Option<Vec<TagParam>>,
+    #[schemars(description = "rename 使用的名称模板，支持 {name}（原名称）、{id}（成员 ID）、{n}（序号）")]
+    pub name_template: Option<String>,
+    #[schemars(description = "最大并发数，1-16，默认 4（可选）")]
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CentralFindMembersParam {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "按授权状态筛选（可选）")]
+    pub authorized: Option<bool>,
+    #[schemars(description = "名称正则表达式，如 ^ci-（可选）")]
+    pub name_regex: Option<String>,
+    #[schemars(description = "描述正则表达式（可选）")]
+    pub description_regex: Option<String>,
+    #[schemars(description = "指定 IP 所在网段 CIDR，如 10.147.20.0/24（可选）")]
+    pub ip_cidr: Option<String>,
+    #[schemars(description = "只选择最近多少分钟内在线过的成员（可选）")]
+    pub seen_within_minutes: Option<u64>,
+    #[schemars(description = "只选择超过多少分钟未在线的成员，含从未在线的成员（可选）")]
+    pub not_seen_for_minutes: Option<u64>,
+    #[schemars(description = "客户端版本下限，如 1.12（可选）")]
+    pub min_client_version: Option<String>,
+    #[schemars(description = "客户端版本上限（含），如 1.14.2（可选）")]
+    pub max_client_version: Option<String>,
+    #[schemars(description = "物理地址通配符，如 203.0.113.*（可选）")]
+    pub physical_address: Option<String>,
+    #[schemars(description = "按是否允许作为以太网桥筛选（可选）")]
+    pub active_bridge: Option<bool>,
+    #[schemars(description = "只选择带有该标签 ID 的成员（可选）")]
+    pub tag_id: Option<i32>,
+    #[schemars(description = "与 tag_id 一起使用，要求标签值相同（可选）")]
+    pub tag_value: Option<i32>,
+    #[schemars(description = "排序字段：name、id、last_seen、client_version 或 ip，默认 id（可选）")]
+    pub sort: Option<String>,
+    #[schemars(description = "是否降序（可选）")]
+    pub descending: Option<bool>,
+    #[schemars(description = "跳过前多少个结果，用于分页（可选）")]
+    pub offset: Option<usize>,
+    #[schemars(description = "最多返回的成员数，默认 50，最大 500（可选）")]
+    pub limit: Option<usize>,
+}
+
+// ============================================
+// 参数校验
+// ============================================
+
+fn parse_ip(value: &str) -> Result<IpAddr, ToolError> {
+    value.trim().parse().map_err(|_| ToolError::invalid_params(tr!("无效的 IP 地址: {}", value)))
+}
+
+fn parse_cidr(value: &str) -> Result<Cidr, ToolError> {
+    value.parse().map_err(|_| ToolError::invalid_params(tr!("无效的网段 CIDR: {}", value)))
+}
+
+pub(super) fn validate_name(name: &str) -> Result<(), ToolError> {
+    if name.trim().is_empty() {
+        return Err(ToolError::invalid_params("网络名称不能为空"));
+    }
+    Ok(())
+}
+
+pub(super) fn validate_mtu(mtu: i32) -> Result<(), ToolError> {
+    if !MTU_RANGE.contains(&mtu) {
+        return Err(ToolError::invalid_params(tr!("MTU {} 超出范围（{}-{}）", mtu, MTU_RANGE.start(), MTU_RANGE.end())));
+    }
+    Ok(())
+}
+
+fn routes(params: &[RouteParam]) -> Result<Vec<Route>, ToolError> {
+    params
+        .iter()
+        .map(|route| {
+            let target = parse_cidr(&route.target)?;
+            if let Some(via) = &route.via {
+                if parse_ip(via)?.is_ipv6() != target.is_ipv6() {
+                    return Err(ToolError::invalid_params(tr!("路由 {} 的网关 {} 与目标地址族不一致", route.target, via)));
+                }
+            }
+            Ok(Route { target: target.to_string(), via: route.via.clone() })
+        })
+        .collect()
+}
+
+fn pools(params: &[PoolParam]) -> Result<Vec<IpAssignmentPool>, ToolError> {
+    params
+        .iter()
+        .map(|pool| {
+            let (start, end) = (parse_ip(&pool.start)?, parse_ip(&pool.end)?);
+            let ordered = match (start, end) {
+                (IpAddr::V4(s), IpAddr::V4(e)) => s <= e,
+                (IpAddr::V6(s), IpAddr::V6(e)) => s <= e,
+                _ => false,
+            };
+            if !ordered {
+                return Err(ToolError::invalid_params(tr!("无效的 IP 分配池: {} - {}", pool.start, pool.end)));
+            }
+            Ok(IpAssignmentPool { ip_range_start: start.to_string(), ip_range_end: end.to_string() })
+        })
+        .collect()
+}
+
+fn dns(domain: Option<&String>, servers: Option<&Vec<String>>) -> Result<Option<Dns>, ToolError> {
+    match (domain, servers) {
+        (None, None) => Ok(None),
+        (Some(domain), Some(servers)) => {
+            if domain.is_empty() != servers.is_empty() {
+                return Err(ToolError::invalid_params("dns_domain 与 dns_servers 须同时为空或同时非空"));
+            }
+            let servers = servers.iter().map(|s| parse_ip(s).map(|ip| ip.to_string())).collect::<Result<_, _>>()?;
+            Ok(Some(Dns { domain: domain.clone(), servers }))
+        }
+        _ => Err(ToolError::invalid_params("dns_domain 与 dns_servers 须同时提供")),
+    }
+}
+
+impl CentralBulkMembersParam {
+    /// 校验并生成批量操作
+    fn action(&self) -> Result<BulkAction, ToolError> {
+        Ok(match self.action.as_str() {
+            "authorize" => BulkAction::Authorize,
+            "deauthorize" => BulkAction::Deauthorize,
+            "delete" => BulkAction::Delete,
+            "set_tags" => {
+                let Some(tags) = &self.tags else {
+                    return Err(ToolError::invalid_params("set_tags 需要提供 tags"));
+                };
+                BulkAction::SetTags(tags.iter().map(|t| (t.id, t.value)).collect())
+            }
+            "rename" => match self.name_template.as_deref().map(str::trim) {
+                Some(template) if !template.is_empty() => BulkAction::Rename(template.to_string()),
+                _ => return Err(ToolError::invalid_params("rename 需要提供非空的 name_template")),
+            },
+            other => {
+                return Err(ToolError::invalid_params(tr!(
+                    "未知的批量操作 \"{}\"，可用: authorize、deauthorize、set_tags、delete、rename",
+                    other
+                )))
+            }
+        })
+    }
+
+    /// 校验并生成筛选条件，至少需要一个条件以免误操作全部成员
+    pub(super) fn filter(&self) -> Result<MemberFilter, ToolError> {
+        let mut filter = MemberFilter::new();
+        if let Some(ids) = &self.member_ids {
+            filter = filter.member_ids(ids.iter().map(|id| id.trim()));
+        }
+        if let Some(authorized) = self.authorized {
+            filter = filter.authorized(authorized);
+        }
+        if let Some(pattern) = &self.name_pattern {
+            filter = filter.name_matches(pattern.trim());
+        }
+        if let Some(minutes) = self.seen_within_minutes {
+            filter = filter.seen_within(Duration::from_secs(minutes * 60));
+        }
+        if filter.is_empty() {
+            return Err(ToolError::invalid_params(
+                "请至少指定一个筛选条件（member_ids、authorized、name_pattern 或 seen_within_minutes）",
+            ));
+        }
+        Ok(filter)
+    }
+
+    fn concurrency(&self) -> Result<usize, ToolError> {
+        match self.concurrency {
+            Some(n) if !BULK_CONCURRENCY.contains(&n) => Err(ToolError::invalid_params(tr!(
+                "并发数 {} 超出范围（{}-{}）",
+                n,
+                BULK_CONCURRENCY.start(),
+                BULK_CONCURRENCY.end()
+            ))),
+            Some(n) => Ok(n),
+            None => Ok(4),
+        }
+    }
+}
+
+fn parse_regex(value: &str) -> Result<Regex, ToolError> {
+    Regex::new(value).map_err(|e| ToolError::invalid_params(tr!("无效的正则表达式 {}: {}", value, e)))
+}
+
+fn parse_version(value: &str) -> Result<ClientVersion, ToolError> {
+    value.parse().map_err(|_| ToolError::invalid_params(tr!("无效的版本号: {}（应为 1.12 或 1.12.2 的形式）", value)))
+}
+
+impl CentralFindMembersParam {
+    /// 校验参数并生成查询
+    fn query(&self) -> Result<MemberQuery, ToolError> {
+        let mut filter = MemberFilter::new();
+        if let Some(authorized) = self.authorized {
+            filter = filter.authorized(authorized);
+        }
+        if let Some(regex) = &self.name_regex {
+            filter = filter.name_regex(parse_regex(regex)?);
+        }
+        if let Some(regex) = &self.description_regex {
+            filter = filter.description_regex(parse_regex(regex)?);
+        }
+        if let Some(cidr) = &self.ip_cidr {
+            filter = filter.ip_in(parse_cidr(cidr)?);
+        }
+        if let Some(minutes) = self.seen_within_minutes {
+            filter = filter.seen_within(Duration::from_secs(minutes * 60));
+        }
+        if let Some(minutes) = self.not_seen_for_minutes {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64);
+            filter = filter.seen_before(now - minutes as i64 * 60_000);
+        }
+        if let Some(version) = &self.min_client_version {
+            filter = filter.min_client_version(parse_version(version)?);
+        }
+        if let Some(version) = &self.max_client_version {
+            filter = filter.max_client_version(parse_version(version)?);
+        }
+        if let Some(pattern) = &self.physical_address {
+            filter = filter.physical_address(pattern.trim());
+        }
+        if let Some(active_bridge) = self.active_bridge {
+            filter = filter.active_bridge(active_bridge);
+        }
+        filter = match (self.tag_id, self.tag_value) {
+            (Some(id), Some(value)) => filter.tag(id, value),
+            (Some(id), None) => filter.has_tag(id),
+            (None, Some(_)) => return Err(ToolError::invalid_params("tag_value 需要与 tag_id 一起使用")),
+            (None, None) => filter,
+        };
+
+        let mut query = MemberQuery::new().filter(filter).descending(self.descending.unwrap_or(false));
+        if let Some(sort) = &self.sort {
+            query = query.sort_by(match sort.as_str() {
+                "name" => MemberSort::Name,
+                "id" => MemberSort::NodeId,
+                "last_seen" => MemberSort::LastSeen,
+                "client_version" => MemberSort::ClientVersion,
+                "ip" => MemberSort::Ip,
+                other => {
+                    return Err(ToolError::invalid_params(tr!(
+                        "未知的排序字段 \"{}\"，可用: name、id、last_seen、client_version、ip",
+                        other
+                    )))
+                }
+            });
+        }
+        let limit = self.limit.unwrap_or(FIND_DEFAULT_LIMIT);
+        if limit == 0 || limit > FIND_MAX_LIMIT {
+            return Err(ToolError::invalid_params(tr!("limit {} 超出范围（1-{}）", limit, FIND_MAX_LIMIT)));
+        }
+        Ok(query.offset(self.offset.unwrap_or(0)).limit(limit))
+    }
+}
+
+/// 查询结果摘要：每个成员一行
+fn find_summary(network_id: &str, offset: usize, result: &MemberQueryResult) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64);
+    let mut lines = vec![if offset == 0 {
+        tr!("网络 {} 中匹配 {} 个成员，显示 {} 个", network_id, result.total, result.members.len())
+    } else {
+        tr!(
+            "网络 {} 中匹配 {} 个成员，跳过 {} 个后显示 {} 个",
+            network_id,
+            result.total,
+            offset,
+            result.members.len()
+        )
+    }];
+    for member in &result.members {
+        let config = member.config.as_ref();
+        let state = if config.is_some_and(|c| c.authorized) { tr!("已授权") } else { tr!("未授权") };
+        let mut line = format!("  {} {} [{}]", member.node_id, member.name, state);
+        for field in [config.map(|c| c.ip_assignments.join(", ")).unwrap_or_default(), member.client_version.clone()] {
+            if !field.is_empty() {
+                line.push(' ');
+                line.push_str(&field);
+            }
+        }
+        line.push(' ');
+        line.push_str(&format_age((member.last_seen > 0).then(|| (now - member.last_seen).max(0))));
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn bulk_summary(report: &BulkReport) -> String {
+    if report.matched == 0 {
+        return tr!("没有匹配筛选条件的成员").to_string();
+    }
+    let mut lines = vec![tr!(
+        "对 {} 个成员执行 {}：成功 {}，失败 {}",
+        report.matched,
+        report.action,
+        report.succeeded,
+        report.failed
+    )];
+    if report.results.iter().any(|r| r.planned.is_some()) {
+        lines.push(tr!("演练模式，未执行写操作").to_string());
+    }
+    for result in &report.results {
+        let status = match (&result.error, &result.new_name) {
+            (Some(error), _) => tr!("失败: {}", error),
+            (None, Some(name)) => format!("-> {}", name),
+            (None, None) => tr!("成功").to_string(),
+        };
+        lines.push(format!("  {} ({}) {}", result.member_id, result.name, status));
+    }
+    lines.join("\n")
+}
+
+impl CentralUpdateNetworkParam {
+    /// 校验参数并生成更新请求
+    fn request(&self) -> Result<UpdateNetworkRequest, ToolError> {
+        if let Some(name) = &self.name {
+            validate_name(name)?;
+        }
+        if let Some(mtu) = self.mtu {
+            validate_mtu(mtu)?;
+        }
+        let config = CreateNetworkConfig {
+            name: self.name.clone(),
+            private: self.private,
+            mtu: self.mtu,
+            routes: self.routes.as_deref().map(routes).transpose()?,
+            ip_assignment_pools: self.ip_assignment_pools.as_deref().map(pools).transpose()?,
+            dns: dns(self.dns_domain.as_ref(), self.dns_servers.as_ref())?,
+            ..Default::default()
+        };
+        let has_config = config.name.is_some()
+            || config.private.is_some()
+            || config.mtu.is_some()
+            || config.routes.is_some()
+            || config.ip_assignment_pools.is_some()
+            || config.dns.is_some();
+        if !has_config && self.description.is_none() {
+            return Err(ToolError::invalid_params("未指定任何要更新的字段"));
+        }
+        Ok(UpdateNetworkRequest {
+            config: has_config.then_some(config),
+            description: self.description.clone(),
+            rules_source: None,
+        })
+    }
+}
+
+impl CentralUpdateMemberParam {
+    /// 校验参数并生成更新请求，不改变授权状态
+    fn request(&self) -> Result<UpdateMemberRequest, ToolError> {
+        let config = (self.active_bridge.is_some() || self.no_auto_assign_ips.is_some()).then(|| UpdateMemberConfig {
+            active_bridge: self.active_bridge,
+            no_auto_assign_ips: self.no_auto_assign_ips,
+            ..Default::default()
+        });
+        if config.is_none() && self.name.is_none() && self.description.is_none() {
+            return Err(ToolError::invalid_params("未指定任何要更新的字段"));
+        }
+        Ok(UpdateMemberRequest { name: self.name.clone(), description: self.description.clone(), config })
+    }
+}
+
+fn yes_no(v: bool) -> &'static str {
+    if v { tr!("是") } else { tr!("否") }
+}
+
+/// 成员摘要：名称、授权、桥接、自动分配与 IP
+fn member_summary(member: &Member) -> String {
+    let mut lines = vec![tr!("成员 {} ({})", member.node_id, member.name)];
+    if let Some(config) = &member.config {
+        lines.push(tr!(
+            "已授权: {}，桥接: {}，自动分配 IP: {}",
+            yes_no(config.authorized),
+            yes_no(config.active_bridge),
+            yes_no(!config.no_auto_assign_ips)
+        ));
+        if !config.ip_assignments.is_empty() {
+            lines.push(format!("IP: {}", config.ip_assignments.join(", ")));
+        }
+    }
+    if !member.description.is_empty() {
+        lines.push(tr!("描述: {}", member.description));
+    }
+    lines.join("\n")
+}
+
+/// 网络配置摘要：名称、私有标志、MTU、路由、分配池与 DNS
+fn network_summary(network: &Network) -> String {
+    let mut lines = Vec::new();
+    if let Some(config) = &network.config {
+        lines.push(tr!("网络 {} ({})", network.id, config.name));
+        lines.push(tr!("私有: {}，MTU: {}", yes_no(config.private), config.mtu));
+        if !config.routes.is_empty() {
+            let routes: Vec<String> = config
+                .routes
+                .iter()
+                .map(|r| match &r.via {
+                    Some(via) => format!("{} via {}", r.target, via),
+                    None => r.target.clone(),
+                })
+                .collect();
+            lines.push(tr!("路由: {}", routes.join(", ")));
+        }
+        if !config.ip_assignment_pools.is_empty() {
+            let pools: Vec<String> = config
+                .ip_assignment_pools
+                .iter()
+                .map(|p| format!("{} - {}", p.ip_range_start, p.ip_range_end))
+                .collect();
+            lines.push(tr!("IP 分配池: {}", pools.join(", ")));
+        }
+        if let Some(dns) = config.dns.as_ref().filter(|d| !d.domain.is_empty()) {
+            lines.push(format!("DNS: {} ({})", dns.domain, dns.servers.join(", ")));
+        }
+    } else {
+        lines.push(tr!("网络 {}", network.id));
+    }
+    if !network.description.is_empty() {
+        lines.push(tr!("描述: {}", network.description));
+    }
+    lines.join("\n")
+}
+
+// ============================================
+// 工具实现
+// ============================================
+
+#[tool_router(router = central_tool_router, vis = "pub(super)")]
+impl McpServer {
+    /// 创建云端网络
+    #[tool(
+        description = "在 Central 创建网络，可通过 CIDR 自动配置路由与 IP 分配池",
+        output_schema = schema::<Network>(),
+        annotations(read_only_hint = false, destructive_hint = false, idempotent_hint = false)
+    )]
+    async fn zt_central_create_network(&self, Parameters(param): Parameters<CentralCreateNetworkParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        if let Err(err) = validate_name(&param.name) {
+            return failure(err);
+        }
+
+        let private = param.private.unwrap_or(true);
+        let config = match &param.cidr {
+            Some(cidr) => {
+                let setup = NetworkSetup::from_cidr(cidr).map(|s| s.name(param.name.clone()).private(private));
+                match setup.and_then(|s| s.central_config()) {
+                    Ok(config) => config,
+                    Err(e) => return failure(ToolError::invalid_params(tr!("网段配置无效: {}", e))),
+                }
+            }
+            None => CreateNetworkConfig { name: Some(param.name.clone()), private: Some(private), ..Default::default() },
+        };
+        let req = UpdateNetworkRequest { config: Some(config), description: param.description, rules_source: None };
+
+        match client.networks().create_with(&req).await {
+            Ok(n) => {
+                let summary = tr!("已创建网络:\n{}", network_summary(&n));
+                structured(&n, summary)
+            }
+            Err(e) => failure(ToolError::central("创建网络失败", &e)),
+        }
+    }
+
+    /// 更新云端网络配置
+    #[tool(
+        description = "更新 Central 网络的名称、描述、私有标志、MTU、路由、IP 分配池或 DNS；路由与分配池整体替换",
+        output_schema = schema::<Network>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_central_update_network(&self, Parameters(param): Parameters<CentralUpdateNetworkParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        let req = match param.request() {
+            Ok(req) => req,
+            Err(err) => return failure(err),
+        };
+
+        match client.networks().update_with(&param.network_id, &req).await {
+            Ok(n) => {
+                let summary = tr!("已更新网络:\n{}", network_summary(&n));
+                structured(&n, summary)
+            }
+            Err(e) => failure(ToolError::central("更新网络失败", &e)),
+        }
+    }
+
+    /// 更新云端网络流规则
+    #[tool(
+        description = "替换 Central 网络的流规则源码（ZeroTier 规则语言），由 Central 编译生效",
+        output_schema = schema::<Network>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_central_update_rules(&self, Parameters(param): Parameters<CentralUpdateRulesParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        if param.rules_source.trim().is_empty() {
+            return failure(ToolError::invalid_params("流规则源码不能为空"));
+        }
+
+        match client.networks().update_rules_source(&param.network_id, param.rules_source).await {
+            Ok(n) => {
+                let rules = n.config.as_ref().map_or(0, |c| c.rules.len());
+                let summary = tr!("已更新流规则，编译后共 {} 条规则:\n{}", rules, network_summary(&n));
+                structured(&n, summary)
+            }
+            Err(e) => failure(ToolError::central("更新流规则失败", &e)),
+        }
+    }
+
+    /// 删除云端网络
+    #[tool(
+        description = "删除 Central 网络及其全部成员",
+        output_schema = schema::<NetworkRef>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_central_delete_network(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+
+        match client.networks().delete(&param.network_id).await {
+            Ok(()) => {
+                let summary = tr!("已删除网络: {}", param.network_id);
+                structured(&NetworkRef { network_id: param.network_id }, summary)
+            }
+            Err(e) => failure(ToolError::central("删除网络失败", &e)),
+        }
+    }
+
+    /// 更新云端成员信息
+    #[tool(
+        description = "修改 Central 网络成员的名称、描述、桥接或自动分配 IP 设置，不改变授权状态",
+        output_schema = schema::<Member>(),
+        annotations(read_only_hint = false, destructive_hint = false, idempotent_hint = true)
+    )]
+    async fn zt_central_update_member(&self, Parameters(param): Parameters<CentralUpdateMemberParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        let req = match param.request() {
+            Ok(req) => req,
+            Err(err) => return failure(err),
+        };
+
+        match client.networks().members(&param.network_id).update(&param.member_id, &req).await {
+            Ok(member) => {
+                let summary = tr!("已更新成员:\n{}", member_summary(&member));
+                structured(&member, summary)
+            }
+            Err(e) => failure(ToolError::central("更新成员失败", &e)),
+        }
+    }
+
+    /// 设置云端成员 IP
+    #[tool(
+        description = "设置或清除 Central 网络成员的指定 IP，不改变授权状态",
+        output_schema = schema::<Member>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_central_set_member_ips(&self, Parameters(param): Parameters<CentralSetMemberIpsParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        let ips = match param.ip_addresses.iter().map(|ip| parse_ip(ip).map(|ip| ip.to_string())).collect() {
+            Ok(ips) => ips,
+            Err(err) => return failure(err),
+        };
+        let req = UpdateMemberRequest {
+            config: Some(UpdateMemberConfig { ip_assignments: Some(ips), ..Default::default() }),
+            ..Default::default()
+        };
+
+        match client.networks().members(&param.network_id).update(&param.member_id, &req).await {
+            Ok(member) => {
+                let summary = tr!("已设置成员 IP:\n{}", member_summary(&member));
+                structured(&member, summary)
+            }
+            Err(e) => failure(ToolError::central("设置成员 IP 失败", &e)),
+        }
+    }
+
+    /// 删除云端成员
+    #[tool(
+        description = "从 Central 网络中删除成员，设备再次加入时需重新授权",
+        output_schema = schema::<MemberRef>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_central_delete_member(&self, Parameters(param): Parameters<MemberParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+
+        match client.networks().members(&param.network_id).delete(&param.member_id).await {
+            Ok(()) => {
+                let summary = tr!("已删除成员: {}（网络 {}）", param.member_id, param.network_id);
+                structured(&MemberRef { network_id: param.network_id, member_id: param.member_id }, summary)
+            }
+            Err(e) => failure(ToolError::central("删除成员失败", &e)),
+        }
+    }
+
+    /// 查询云端成员
+    #[tool(
+        description = "按条件查询 Central 网络成员：授权状态、名称/描述正则、IP 网段、最后在线时间、客户端版本范围、物理地址、桥接与标签，支持排序与分页，适合成员较多的网络",
+        output_schema = schema::<MemberQueryResult>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_central_find_members(&self, Parameters(param): Parameters<CentralFindMembersParam>) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        let query = match param.query() {
+            Ok(query) => query,
+            Err(e) => return failure(e),
+        };
+
+        match client.networks().members(&param.network_id).find(&query).await {
+            Ok(result) => {
+                let summary = find_summary(&param.network_id, param.offset.unwrap_or(0), &result);
+                structured(&result, summary)
+            }
+            Err(e) => failure(ToolError::central("获取成员失败", &e)),
+        }
+    }
+
+    /// 批量操作云端成员
+    #[tool(
+        description = "按条件筛选 Central 网络成员，批量授权、取消授权、设置标签、删除或按模板重命名；以有限并发执行并逐个报告结果，部分失败不影响其余成员。请求携带 progressToken 时发送进度通知",
+        output_schema = schema::<BulkReport>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = false)
+    )]
+    async fn zt_central_bulk_members(
+        &self,
+        Parameters(param): Parameters<CentralBulkMembersParam>,
+        context: RequestContext<RoleServer>,
+    ) -> ToolResult {
+        let Some(ref client) = self.central_client else {
+            return failure(ToolError::central_not_configured());
+        };
+        let checked = param.action().and_then(|a| Ok((a, param.filter()?, param.concurrency()?)));
+        let (action, filter, concurrency) = match checked {
+            Ok(checked) => checked,
+            Err(e) => return failure(e),
+        };
+
+        let mut operation =
+            client.networks().members(&param.network_id).bulk(action).filter(filter).concurrency(concurrency);
+        // 进度通知经通道按完成顺序转发，返回结果前等待全部发出
+        let mut forward = None;
+        if let Some(token) = context.meta.get_progress_token() {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let peer = context.peer.clone();
+            forward = Some(tokio::spawn(async move {
+                while let Some(param) = rx.recv().await {
+                    if peer.notify_progress(param).await.is_err() {
+                        break;
+                    }
+                }
+            }));
+            operation = operation.on_progress(move |progress| {
+                let status = if progress.ok { tr!("成功") } else { tr!("失败") };
+                let _ = tx.send(ProgressNotificationParam {
+                    progress_token: token.clone(),
+                    progress: progress.completed as f64,
+                    total: Some(progress.total as f64),
+                    message: Some(format!("{} {}", progress.member_id, status)),
+                });
+            });
+        }
+
+        let result = operation.run().await;
+        if let Some(forward) = forward {
+            let _ = forward.await;
+        }
+        match result {
+            Ok(report) => {
+                let summary = bulk_summary(&report);
+                structured(&report, summary)
+            }
+            Err(e) => failure(ToolError::central("获取成员失败", &e)),
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! 破坏性操作的二次确认
+//!
+//! 启用后，离开网络、取消授权与删除类工具的首次调用只返回影响预览和一个短期有效的
+//! `confirm_token`，携带该令牌以相同参数再次调用才会真正执行，避免模型误读 ID 后直接操作。
+
+use super::central::CentralBulkMembersParam;
+use super::error::{failure, ToolError};
+use super::i18n::{self, tr};
+use super::output::{schema, structured, ToolResult};
+use super::server::{str_arg, McpServer};
+use rmcp::{
+    model::{CallToolRequestParam, JsonObject, Tool},
+    schemars::{self, JsonSchema},
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// 确认令牌默认有效期
+pub(super) const DEFAULT_CONFIRMATION_TTL: Duration = Duration::from_secs(120);
+
+/// 确认令牌参数名
+const TOKEN_ARG: &str = "confirm_token";
+
+/// 需要二次确认的工具：离开网络、取消授权、批量操作以及所有删除类工具
+pub(super) fn needs_confirmation(tool: &str) -> bool {
+    matches!(tool, "zt_leave" | "zt_central_deauthorize" | "zt_controller_deauthorize" | "zt_central_bulk_members")
+        || tool.contains("_delete")
+}
+
+/// 操作预览
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct ConfirmationPreview {
+    /// 恒为 true，表示操作尚未执行
+    pub confirmation_required: bool,
+    pub tool: String,
+    /// 将要执行的操作
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_name: Option<String>,
+    /// 受影响的 IP
+    pub ip_assignments: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized: Option<bool>,
+    /// 目标是否为本机节点
+    pub is_self: bool,
+    /// 删除网络或批量操作时受影响的成员数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<usize>,
+    /// 预览信息获取失败等提示（如成员不存在，可能是 ID 有误）
+    pub warnings: Vec<String>,
+    /// 以相同参数再次调用时携带此令牌以执行操作
+    pub confirm_token: String,
+    pub expires_in_secs: u64,
+}
+
+struct Pending {
+    tool: String,
+    arguments: JsonObject,
+    expires_at: Instant,
+}
+
+/// 待确认的操作（按会话隔离）
+#[derive(Clone, Default)]
+pub(super) struct Confirmations {
+    pending: Arc<Mutex<HashMap<String, Pending>>>,
+}
+
+impl Confirmations {
+    fn issue(&self, tool: &str, arguments: JsonObject, ttl: Duration) -> String {
+        let token = new_token();
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|_, p| p.expires_at > now);
+        pending.insert(token.clone(), Pending { tool: tool.to_string(), arguments, expires_at: now + ttl });
+        token
+    }
+
+    /// 校验并消费令牌
+    fn take(&self, token: &str, tool: &str, arguments: &JsonObject) -> Result<(), ToolError> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = pending.remove(token) else {
+            return Err(ToolError::invalid_params("确认令牌无效或已使用，请不带 confirm_token 重新调用以获取预览"));
+        };
+        if entry.expires_at <= Instant::now() {
+            return Err(ToolError::invalid_params("确认令牌已过期，请不带 confirm_token 重新调用以获取预览"));
+        }
+        if entry.tool != tool || &entry.arguments != arguments {
+            return Err(ToolError::invalid_params(tr!(
+                "确认令牌属于 {} 的另一组参数，与本次调用不符，请重新获取预览",
+                entry.tool
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 生成令牌；仅用于防止误操作，不作为安全凭据
+fn new_token() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos()));
+    format!("{:016x}", hasher.finish())
+}
+
+impl McpServer {
+    /// 二次确认：返回 `Some` 时直接作为调用结果（预览或错误），`None` 时继续执行
+    ///
+    /// 携带有效令牌时会从参数中移除 `confirm_token`。
+    pub(super) async fn confirm_tool(&self, request: &mut CallToolRequestParam) -> Option<ToolResult> {
+        let ttl = self.confirmation_ttl?;
+        if !needs_confirmation(&request.name) {
+            return None;
+        }
+        let mut arguments = request.arguments.take().unwrap_or_default();
+        let token = arguments.remove(TOKEN_ARG);
+
+        if let Some(token) = token {
+            let result = match token.as_str() {
+                Some(token) => self.confirmations.take(token, &request.name, &arguments),
+                None => Err(ToolError::invalid_params("confirm_token 必须是字符串")),
+            };
+            request.arguments = Some(arguments);
+            return result.err().map(failure);
+        }
+
+        let mut preview = self.preview(&request.name, &arguments).await;
+        preview.confirm_token = self.confirmations.issue(&request.name, arguments, ttl);
+        preview.expires_in_secs = ttl.as_secs();
+        let summary = preview_summary(&preview);
+        Some(structured(&preview, summary))
+    }
+
+    /// 收集操作影响的信息，获取失败时写入 `warnings`
+    async fn preview(&self, tool: &str, arguments: &JsonObject) -> ConfirmationPreview {
+        let network_id = str_arg(arguments, "network_id").map(str::to_string);
+        let member_id = str_arg(arguments, "member_id").map(str::to_string);
+        let mut preview = ConfirmationPreview {
+            confirmation_required: true,
+            tool: tool.to_string(),
+            network_id: network_id.clone(),
+            member_id: member_id.clone(),
+            ..Default::default()
+        };
+        let self_address = match self.local_client.status().await {
+            Ok(status) => Some(status.address),
+            Err(e) => {
+                preview.warnings.push(tr!("无法获取本机节点 ID: {}", e));
+                None
+            }
+        };
+        let network_id = network_id.unwrap_or_default();
+        let member_id = member_id.unwrap_or_default();
+
+        match tool {
+            "zt_leave" => {
+                preview.action = tr!("本机离开网络 {}", network_id);
+                preview.member_id = self_address.clone();
+                preview.is_self = true;
+                match self.local_client.networks().get(&network_id).await {
+                    Ok(network) => {
+                        preview.network_name = Some(network.name);
+                        preview.ip_assignments = network.assigned_addresses;
+                    }
+                    Err(e) => preview.warnings.push(tr!("本机未加入该网络或无法读取: {}", e)),
+                }
+            }
+            "zt_central_deauthorize" | "zt_central_delete_member" => {
+                preview.action = if tool == "zt_central_deauthorize" {
+                    tr!("在 Central 取消授权成员 {}（网络 {}）", member_id, network_id)
+                } else {
+                    tr!("从 Central 删除成员 {}（网络 {}）", member_id, network_id)
+                };
+                if let Some(central) = &self.central_client {
+                    match central.networks().get(&network_id).await {
+                        Ok(network) => preview.network_name = network.config.map(|c| c.name),
+                        Err(e) => preview.warnings.push(tr!("无法读取网络: {}", e)),
+                    }
+                    match central.networks().members(&network_id).get(&member_id).await {
+                        Ok(member) => {
+                            preview.member_name = Some(member.name);
+                            if let Some(config) = member.config {
+                                preview.ip_assignments = config.ip_assignments;
+                                preview.authorized = Some(config.authorized);
+                            }
+                        }
+                        Err(e) => preview.warnings.push(tr!("成员不存在或无法读取（请核对 ID）: {}", e)),
+                    }
+                }
+            }
+            "zt_central_delete_network" => {
+                preview.action = tr!("在 Central 删除网络 {} 及其全部成员", network_id);
+                if let Some(central) = &self.central_client {
+                    match central.networks().get(&network_id).await {
+                        Ok(network) => {
+                            preview.network_name = network.config.map(|c| c.name);
+                            preview.member_count = Some(network.total_member_count.max(0) as usize);
+                        }
+                        Err(e) => preview.warnings.push(tr!("网络不存在或无法读取（请核对 ID）: {}", e)),
+                    }
+                }
+            }
+            "zt_central_bulk_members" => {
+                let action = str_arg(arguments, "action").unwrap_or_default();
+                let filter = serde_json::from_value::<CentralBulkMembersParam>(Value::Object(arguments.clone()))
+                    .map_err(|e| e.to_string())
+                    .and_then(|param| param.filter().map_err(|e| e.message));
+                preview.action = tr!("在 Central 对网络 {} 中匹配的成员批量执行 {}", network_id, action);
+                match (&self.central_client, filter) {
+                    (Some(central), Ok(filter)) => {
+                        match central.networks().get(&network_id).await {
+                            Ok(network) => preview.network_name = network.config.map(|c| c.name),
+                            Err(e) => preview.warnings.push(tr!("无法读取网络: {}", e)),
+                        }
+                        match central.networks().members(&network_id).list().await {
+                            Ok(members) => {
+                                let matched = filter.apply(members);
+                                if matched.is_empty() {
+                                    preview.warnings.push(tr!("没有匹配筛选条件的成员").to_string());
+                                }
+                                preview.is_self = matched
+                                    .iter()
+                                    .any(|m| self_address.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(&m.node_id)));
+                                preview.member_count = Some(matched.len());
+                                preview.ip_assignments =
+                                    matched.into_iter().filter_map(|m| m.config).flat_map(|c| c.ip_assignments).collect();
+                            }
+                            Err(e) => preview.warnings.push(tr!("无法读取成员: {}", e)),
+                        }
+                    }
+                    (_, Err(e)) => preview.warnings.push(e),
+                    (None, _) => {}
+                }
+            }
+            "zt_controller_deauthorize" | "zt_controller_delete_member" => {
+                preview.action = if tool == "zt_controller_deauthorize" {
+                    tr!("在本机控制器取消授权成员 {}（网络 {}）", member_id, network_id)
+                } else {
+                    tr!("从本机控制器删除成员 {}（网络 {}）", member_id, network_id)
+                };
+                let controller = self.local_client.controller();
+                match controller.get_network(&network_id).await {
+                    Ok(network) => preview.network_name = Some(network.name),
+                    Err(e) => preview.warnings.push(tr!("无法读取网络: {}", e)),
+                }
+                match controller.get_member(&network_id, &member_id).await {
+                    Ok(member) => {
+                        preview.ip_assignments = member.ip_assignments;
+                        preview.authorized = Some(member.authorized);
+                    }
+                    Err(e) => preview.warnings.push(tr!("成员不存在或无法读取（请核对 ID）: {}", e)),
+                }
+            }
+            "zt_controller_delete_network" => {
+                preview.action = tr!("从本机控制器删除网络 {} 及其全部成员", network_id);
+                let controller = self.local_client.controller();
+                match controller.get_network(&network_id).await {
+                    Ok(network) => preview.network_name = Some(network.name),
+                    Err(e) => preview.warnings.push(tr!("网络不存在或无法读取（请核对 ID）: {}", e)),
+                }
+                match controller.list_members(&network_id).await {
+                    Ok(members) => preview.member_count = Some(members.len()),
+                    Err(e) => preview.warnings.push(tr!("无法读取成员: {}", e)),
+                }
+            }
+            _ => preview.action = tr!("执行 {}", tool),
+        }
+
+        if tool != "zt_leave" && tool != "zt_central_bulk_members" {
+            preview.is_self = self_address.is_some_and(|a| a.eq_ignore_ascii_case(&member_id));
+        }
+        preview
+    }
+
+    /// 启用二次确认时，为工具补充 `confirm_token` 参数与预览输出的 schema（演练模式下不需要确认）
+    pub(super) fn confirmation_schema(&self, mut tool: Tool) -> Tool {
+        if self.confirmation_ttl.is_none() || self.dry_run || !needs_confirmation(&tool.name) {
+            return tool;
+        }
+        let mut input = (*tool.input_schema).clone();
+        if let Some(Value::Object(properties)) = input.get_mut("properties") {
+            properties.insert(
+                TOKEN_ARG.to_string(),
+                json!({
+                    "type": "string",
+                    "description": tr!("确认令牌：首次调用不带此参数会返回预览与令牌，确认无误后以相同参数携带令牌再次调用才会执行")
+                }),
+            );
+        }
+        tool.input_schema = Arc::new(input);
+
+        let preview = schema::<ConfirmationPreview>();
+        tool.output_schema = Some(Arc::new(match &tool.output_schema {
+            Some(result) => any_of(result, &preview),
+            None => (*preview).clone(),
+        }));
+        if let Some(description) = &tool.description {
+            tool.description = Some(tr!("{}（需二次确认：首次调用返回预览与 confirm_token）", i18n::lookup(description)).into());
+        }
+        tool
+    }
+}
+
+/// 合并两个 schema 为 `anyOf`，并把 `definitions` 提到根部以保证 `$ref` 可解析
+pub(super) fn any_of(a: &JsonObject, b: &JsonObject) -> JsonObject {
+    let mut definitions = JsonObject::new();
+    let branches: Vec<Value> = [a, b]
+        .into_iter()
+        .map(|schema| {
+            let mut schema = schema.clone();
+            schema.remove("$schema");
+            if let Some(Value::Object(defs)) = schema.remove("definitions") {
+                definitions.extend(defs);
+            }
+            Value::Object(schema)
+        })
+        .collect();
+    let mut merged = JsonObject::new();
+    merged.insert("type".to_string(), json!("object"));
+    merged.insert("anyOf".to_string(), Value::Array(branches));
+    if !definitions.is_empty() {
+        merged.insert("definitions".to_string(), Value::Object(definitions));
+    }
+    merged
+}
+
+fn preview_summary(preview: &ConfirmationPreview) -> String {
+    let mut lines = vec![tr!("尚未执行，需要确认：{}", preview.action)];
+    if let Some(name) = &preview.network_name {
+        lines.push(tr!("网络名称: {}", name));
+    }
+    if let Some(name) = preview.member_name.as_deref().filter(|n| !n.is_empty()) {
+        lines.push(tr!("成员名称: {}", name));
+    }
+    if !preview.ip_assignments.is_empty() {
+        lines.push(format!("IP: {}", preview.ip_assignments.join(", ")));
+    }
+    if let Some(authorized) = preview.authorized {
+        lines.push(tr!("当前已授权: {}", if authorized { tr!("是") } else { tr!("否") }));
+    }
+    if let Some(count) = preview.member_count {
+        lines.push(tr!("成员数: {}", count));
+    }
+    if preview.is_self {
+        lines.push(tr!("警告: 目标是本机节点，执行后本机可能失去网络连接").to_string());
+    }
+    for warning in &preview.warnings {
+        lines.push(tr!("警告: {}", warning));
+    }
+    lines.push(tr!(
+        "确认无误后以相同参数并附加 confirm_token = \"{}\" 再次调用（{} 秒内有效）",
+        preview.confirm_token, preview.expires_in_secs
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::error::ErrorKind;
+
+    fn args(network_id: &str) -> JsonObject {
+        json!({ "network_id": network_id, "member_id": "abcdef0123" }).as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn token_is_single_use() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue("zt_central_deauthorize", args("8056c2e21c000001"), DEFAULT_CONFIRMATION_TTL);
+        assert!(confirmations.take(&token, "zt_central_deauthorize", &args("8056c2e21c000001")).is_ok());
+
+        let err = confirmations.take(&token, "zt_central_deauthorize", &args("8056c2e21c000001")).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidParams);
+        assert!(err.message.contains("已使用"), "{}", err.message);
+    }
+
+    #[test]
+    fn token_expires_after_ttl() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue("zt_leave", args("8056c2e21c000001"), Duration::ZERO);
+        let err = confirmations.take(&token, "zt_leave", &args("8056c2e21c000001")).unwrap_err();
+        assert!(err.message.contains("已过期"), "{}", err.message);
+    }
+
+    #[test]
+    fn token_is_bound_to_the_tool() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue("zt_central_deauthorize", args("8056c2e21c000001"), DEFAULT_CONFIRMATION_TTL);
+        let err = confirmations.take(&token, "zt_central_delete_member", &args("8056c2e21c000001")).unwrap_err();
+        assert!(err.message.contains("zt_central_deauthorize"), "{}", err.message);
+        // 不匹配的令牌同样被消费，不能再用于原工具
+        assert!(confirmations.take(&token, "zt_central_deauthorize", &args("8056c2e21c000001")).is_err());
+    }
+
+    #[test]
+    fn token_is_bound_to_the_arguments() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue("zt_central_deauthorize", args("8056c2e21c000001"), DEFAULT_CONFIRMATION_TTL);
+        let err = confirmations.take(&token, "zt_central_deauthorize", &args("8056c2e21c000002")).unwrap_err();
+        assert!(err.message.contains("另一组参数"), "{}", err.message);
+    }
+
+    #[test]
+    fn expired_tokens_are_pruned_on_issue() {
+        let confirmations = Confirmations::default();
+        confirmations.issue("zt_leave", args("8056c2e21c000001"), Duration::ZERO);
+        confirmations.issue("zt_leave", args("8056c2e21c000002"), DEFAULT_CONFIRMATION_TTL);
+        assert_eq!(confirmations.pending.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,345 @@
+use super::central::{validate_mtu, validate_name};
+use super::error::{failure, ToolError};
+use super::i18n::tr;
+use super::output::{
+    schema, structured, ControllerMemberList, ControllerNetworkList, MemberRef, NetworkRef, ToolResult,
+};
+use super::server::{McpServer, MemberParam, NetworkIdParam};
+use crate::client::{
+    ControllerMember, ControllerMemberConfig, ControllerNetwork, ControllerNetworkConfig, ControllerService,
+    ControllerStatus, Error,
+};
+use crate::setup::NetworkSetup;
+use rmcp::{
+    handler::server::wrapper::Parameters,
+    schemars::{self, JsonSchema},
+    tool, tool_router,
+};
+use serde::Deserialize;
+
+// ============================================
+// 工具参数定义
+// ============================================
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ControllerCreateNetworkParam {
+    #[schemars(description = "网络名称")]
+    pub name: String,
+    #[schemars(description = "网段 CIDR（如 10.147.20.0/24），自动生成受管路由与 IP 分配池（可选）")]
+    pub cidr: Option<String>,
+    #[schemars(description = "是否为私有网络，默认 true（可选）")]
+    pub private: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ControllerUpdateNetworkParam {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "新名称（可选）")]
+    pub name: Option<String>,
+    #[schemars(description = "新网段 CIDR，将替换路由与 IP 分配池（可选）")]
+    pub cidr: Option<String>,
+    #[schemars(description = "是否为私有网络（可选）")]
+    pub private: Option<bool>,
+    #[schemars(description = "MTU（可选）")]
+    pub mtu: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ControllerAuthorizeParam {
+    #[schemars(description = "网络 ID")]
+    pub network_id: String,
+    #[schemars(description = "成员 ID")]
+    pub member_id: String,
+    #[schemars(description = "指定 IP 地址列表（可选，如 [\"10.147.20.100\"]）")]
+    pub ip_addresses: Option<Vec<String>>,
+}
+
+impl ControllerUpdateNetworkParam {
+    /// 校验参数并生成网络配置
+    fn config(&self) -> Result<ControllerNetworkConfig, ToolError> {
+        if let Some(name) = &self.name {
+            validate_name(name)?;
+        }
+        if let Some(mtu) = self.mtu {
+            validate_mtu(mtu)?;
+        }
+        if self.name.is_none() && self.cidr.is_none() && self.private.is_none() && self.mtu.is_none() {
+            return Err(ToolError::invalid_params("未指定任何要更新的字段"));
+        }
+        let mut config = match &self.cidr {
+            Some(cidr) => NetworkSetup::from_cidr(cidr)
+                .and_then(|s| s.controller_config())
+                .map_err(|e| ToolError::invalid_params(tr!("网段配置无效: {}", e)))?,
+            None => ControllerNetworkConfig::default(),
+        };
+        config.name = self.name.clone();
+        config.private = self.private;
+        config.mtu = self.mtu;
+        Ok(config)
+    }
+}
+
+// ============================================
+// 工具实现
+// ============================================
+
+impl McpServer {
+    /// 获取本机控制器，未启用时返回提示
+    async fn local_controller(&self) -> Result<ControllerService, ToolError> {
+        let controller = self.local_client.controller();
+        match controller.status().await {
+            Ok(status) if status.controller => Ok(controller),
+            // 未编译控制器的节点对 /controller 返回 404
+            Ok(_) | Err(Error::Api { status: 404, .. }) => Err(ToolError::controller_disabled()),
+            Err(e) => Err(ToolError::local("获取控制器状态失败", &e)),
+        }
+    }
+}
+
+#[tool_router(router = controller_tool_router, vis = "pub(super)")]
+impl McpServer {
+    /// 获取本机控制器状态
+    #[tool(
+        description = "获取本机自托管控制器状态",
+        output_schema = schema::<ControllerStatus>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_controller_status(&self) -> ToolResult {
+        match self.local_client.controller().status().await {
+            Ok(status) => {
+                let summary = tr!(
+                    "控制器{}，API 版本 {}",
+                    if status.controller { tr!("已启用") } else { tr!("未启用") },
+                    status.api_version
+                );
+                structured(&status, summary)
+            }
+            Err(e) => failure(ToolError::local("获取控制器状态失败", &e)),
+        }
+    }
+
+    /// 列出控制器管理的网络
+    #[tool(
+        description = "列出本机控制器管理的网络",
+        output_schema = schema::<ControllerNetworkList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_controller_networks(&self) -> ToolResult {
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        let ids = match controller.list_networks().await {
+            Ok(ids) => ids,
+            Err(e) => return failure(ToolError::local("获取网络失败", &e)),
+        };
+
+        let mut networks = Vec::new();
+        for id in ids {
+            match controller.get_network(&id).await {
+                Ok(n) => networks.push(n),
+                Err(e) => return failure(ToolError::local(&tr!("获取网络 {} 详情失败", id), &e)),
+            }
+        }
+        let summary = tr!("控制器共 {} 个网络", networks.len());
+        structured(&ControllerNetworkList { networks }, summary)
+    }
+
+    /// 创建控制器网络
+    #[tool(
+        description = "在本机控制器上创建网络，可通过 CIDR 自动配置路由与 IP 分配池",
+        output_schema = schema::<ControllerNetwork>(),
+        annotations(read_only_hint = false, destructive_hint = false, idempotent_hint = false)
+    )]
+    async fn zt_controller_create_network(&self, Parameters(param): Parameters<ControllerCreateNetworkParam>) -> ToolResult {
+        if let Err(err) = validate_name(&param.name) {
+            return failure(err);
+        }
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        let config = match &param.cidr {
+            Some(cidr) => {
+                let setup = NetworkSetup::from_cidr(cidr).map(|s| s.name(param.name.clone()).private(param.private.unwrap_or(true)));
+                match setup.and_then(|s| s.controller_config()) {
+                    Ok(config) => config,
+                    Err(e) => return failure(ToolError::invalid_params(tr!("网段配置无效: {}", e))),
+                }
+            }
+            None => ControllerNetworkConfig {
+                name: Some(param.name.clone()),
+                private: Some(param.private.unwrap_or(true)),
+                ..Default::default()
+            },
+        };
+
+        let node_id = match self.local_client.status().await {
+            Ok(status) => status.address,
+            Err(e) => return failure(ToolError::local("获取节点地址失败", &e)),
+        };
+
+        match controller.create_network(&node_id, Some(&config)).await {
+            Ok(n) => {
+                let summary = tr!("已创建网络: {} ({})", n.id, n.name);
+                structured(&n, summary)
+            }
+            Err(e) => failure(ToolError::local("创建网络失败", &e)),
+        }
+    }
+
+    /// 更新控制器网络
+    #[tool(
+        description = "更新本机控制器网络的名称、私有标志、MTU 或网段",
+        output_schema = schema::<ControllerNetwork>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_controller_update_network(&self, Parameters(param): Parameters<ControllerUpdateNetworkParam>) -> ToolResult {
+        let config = match param.config() {
+            Ok(config) => config,
+            Err(err) => return failure(err),
+        };
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        match controller.update_network(&param.network_id, &config).await {
+            Ok(n) => {
+                let summary = tr!("已更新网络: {} ({})", n.id, n.name);
+                structured(&n, summary)
+            }
+            Err(e) => failure(ToolError::local("更新网络失败", &e)),
+        }
+    }
+
+    /// 删除控制器网络
+    #[tool(
+        description = "删除本机控制器上的网络",
+        output_schema = schema::<NetworkRef>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_controller_delete_network(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        match controller.delete_network(&param.network_id).await {
+            Ok(()) => {
+                let summary = tr!("已删除网络: {}", param.network_id);
+                structured(&NetworkRef { network_id: param.network_id }, summary)
+            }
+            Err(e) => failure(ToolError::local("删除网络失败", &e)),
+        }
+    }
+
+    /// 列出控制器网络成员
+    #[tool(
+        description = "列出本机控制器网络的成员",
+        output_schema = schema::<ControllerMemberList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_controller_members(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        let ids = match controller.list_members(&param.network_id).await {
+            Ok(ids) => ids,
+            Err(e) => return failure(ToolError::local("获取成员失败", &e)),
+        };
+
+        let mut members = Vec::new();
+        for id in ids {
+            match controller.get_member(&param.network_id, &id).await {
+                Ok(m) => members.push(m),
+                Err(e) => return failure(ToolError::local(&tr!("获取成员 {} 详情失败", id), &e)),
+            }
+        }
+        let authorized = members.iter().filter(|m| m.authorized).count();
+        let summary = tr!(
+            "网络 {} 共 {} 个成员，已授权 {} 个",
+            param.network_id,
+            members.len(),
+            authorized
+        );
+        structured(&ControllerMemberList { network_id: param.network_id, members }, summary)
+    }
+
+    /// 授权控制器网络成员
+    #[tool(
+        description = "授权本机控制器网络成员，可同时指定 IP 地址",
+        output_schema = schema::<ControllerMember>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_controller_authorize(&self, Parameters(param): Parameters<ControllerAuthorizeParam>) -> ToolResult {
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        let config = ControllerMemberConfig {
+            authorized: Some(true),
+            ip_assignments: param.ip_addresses.clone(),
+            ..Default::default()
+        };
+        match controller.update_member(&param.network_id, &param.member_id, &config).await {
+            Ok(m) => {
+                let summary = tr!("已授权成员: {}，IP: {}", m.address, m.ip_assignments.join(", "));
+                structured(&m, summary)
+            }
+            Err(e) => failure(ToolError::local("授权失败", &e)),
+        }
+    }
+
+    /// 取消控制器网络成员授权
+    #[tool(
+        description = "取消本机控制器网络成员的授权",
+        output_schema = schema::<ControllerMember>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_controller_deauthorize(&self, Parameters(param): Parameters<MemberParam>) -> ToolResult {
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        let config = ControllerMemberConfig {
+            authorized: Some(false),
+            ..Default::default()
+        };
+        match controller.update_member(&param.network_id, &param.member_id, &config).await {
+            Ok(m) => {
+                let summary = tr!("已取消授权: {}", m.address);
+                structured(&m, summary)
+            }
+            Err(e) => failure(ToolError::local("取消授权失败", &e)),
+        }
+    }
+
+    /// 删除控制器网络成员
+    #[tool(
+        description = "从本机控制器网络中删除成员",
+        output_schema = schema::<MemberRef>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_controller_delete_member(&self, Parameters(param): Parameters<MemberParam>) -> ToolResult {
+        let controller = match self.local_controller().await {
+            Ok(c) => c,
+            Err(err) => return failure(err),
+        };
+
+        match controller.delete_member(&param.network_id, &param.member_id).await {
+            Ok(()) => {
+                let summary = tr!("已删除成员: {}", param.member_id);
+                structured(&MemberRef { network_id: param.network_id, member_id: param.member_id }, summary)
+            }
+            Err(e) => failure(ToolError::local("删除成员失败", &e)),
+        }
+    }
+}
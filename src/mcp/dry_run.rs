@@ -0,0 +1,75 @@
+//! 演练模式
+//!
+//! 启用后，写工具照常通过访问策略与网络策略检查，但客户端只读取当前状态、计算预期变更，
+//! 不发送写请求；每次调用使用独立的演练记录，工具执行完毕后以 `{"dry_run": true, "writes": [...]}`
+//! 返回本次调用的全部写请求，供人工审阅后再关闭演练执行。
+
+use super::access::is_mutating;
+use super::confirm::any_of;
+use super::i18n::{self, tr};
+use super::output::{schema, structured, ToolResult};
+use super::server::McpServer;
+use crate::dry_run::{PlannedWrite, PlannedWrites};
+use rmcp::{
+    model::{CallToolResult, Tool},
+    schemars::{self, JsonSchema},
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// 演练结果
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DryRunResult {
+    /// 恒为 true，表示写请求未发送
+    pub dry_run: bool,
+    /// 本次调用中未发送的写请求及预期变更，按发送顺序排列
+    pub writes: Vec<PlannedWrite>,
+}
+
+/// 演练结果：`structuredContent` 为 [`DryRunResult`]，`content` 为变更摘要
+fn dry_run_result(writes: Vec<PlannedWrite>) -> ToolResult {
+    let mut summary = tr!("演练模式，未执行写操作").to_string();
+    if writes.is_empty() {
+        summary.push_str(&format!("\n{}", tr!("没有需要发送的写请求")));
+    }
+    for write in &writes {
+        summary.push_str(&format!("\n{}", write));
+    }
+    structured(&DryRunResult { dry_run: true, writes }, summary)
+}
+
+impl McpServer {
+    /// 使用独立演练记录的副本（本地与云端客户端共享同一份记录），使并发的调用互不混入对方的写请求
+    pub(super) fn dry_run_planner(&self) -> Self {
+        let planned = PlannedWrites::default();
+        Self {
+            local_client: self.local_client.clone().with_planned(planned.clone()),
+            central_client: self.central_client.clone().map(|c| c.with_planned(planned)),
+            ..self.clone()
+        }
+    }
+
+    /// 取出演练副本记录的写请求作为结果；工具本身失败时原样返回错误
+    pub(super) fn dry_run_outcome(&self, result: CallToolResult) -> ToolResult {
+        if result.is_error == Some(true) {
+            return Ok(result);
+        }
+        dry_run_result(self.local_client.take_planned_writes())
+    }
+
+    /// 启用演练模式时，为写工具补充演练结果的输出 schema 与说明
+    pub(super) fn dry_run_schema(&self, mut tool: Tool) -> Tool {
+        if !self.dry_run || !is_mutating(&tool) {
+            return tool;
+        }
+        let result = schema::<DryRunResult>();
+        tool.output_schema = Some(Arc::new(match &tool.output_schema {
+            Some(output) => any_of(output, &result),
+            None => (*result).clone(),
+        }));
+        if let Some(description) = &tool.description {
+            tool.description = Some(tr!("{}（演练模式：不执行写操作，返回预期变更）", i18n::lookup(description)).into());
+        }
+        tool
+    }
+}
@@ -0,0 +1,143 @@
+//! 工具错误结果
+
+use super::i18n;
+use super::output::ToolResult;
+use super::resolve::Candidate;
+use crate::{central, client};
+use rmcp::model::{CallToolResult, Content};
+use serde::Serialize;
+
+/// 错误类别，供调用方据此分支或重试
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Token 无效或权限不足（401 / 403）
+    Unauthorized,
+    /// 网络、成员等资源不存在（404）
+    NotFound,
+    /// 请求过于频繁（429），稍后重试
+    RateLimited,
+    /// 无法连接本地 ZeroTier 服务
+    DaemonUnreachable,
+    /// 无法连接 Central API
+    CentralUnreachable,
+    /// 未配置 Central API Token
+    CentralNotConfigured,
+    /// 本机未启用网络控制器
+    ControllerDisabled,
+    /// 被访问策略拒绝（只读模式、允许/禁止列表）
+    Forbidden,
+    /// 参数无效
+    InvalidParams,
+    /// 响应无法解析
+    InvalidResponse,
+    /// 其他 API 错误
+    Api,
+    /// 名称匹配到多个网络或成员，需从候选项中选择
+    Ambiguous,
+}
+
+impl ErrorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unauthorized => "unauthorized",
+            Self::NotFound => "not_found",
+            Self::RateLimited => "rate_limited",
+            Self::DaemonUnreachable => "daemon_unreachable",
+            Self::CentralUnreachable => "central_unreachable",
+            Self::CentralNotConfigured => "central_not_configured",
+            Self::ControllerDisabled => "controller_disabled",
+            Self::Forbidden => "forbidden",
+            Self::InvalidParams => "invalid_params",
+            Self::InvalidResponse => "invalid_response",
+            Self::Api => "api",
+            Self::Ambiguous => "ambiguous",
+        }
+    }
+
+    /// 稍后重试可能成功
+    pub fn retryable(self) -> bool {
+        matches!(self, Self::RateLimited | Self::DaemonUnreachable | Self::CentralUnreachable)
+    }
+
+    fn from_status(status: u16) -> Self {
+        match status {
+            400 | 422 => Self::InvalidParams,
+            401 | 403 => Self::Unauthorized,
+            404 => Self::NotFound,
+            429 => Self::RateLimited,
+            _ => Self::Api,
+        }
+    }
+}
+
+/// 工具错误，以 `isError = true` 的结果返回给客户端
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// 上游 HTTP 状态码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub retryable: bool,
+    /// 名称匹配到的多个候选项
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<Candidate>,
+}
+
+impl ToolError {
+    /// 固定文本的错误信息按当前语言翻译
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let message = i18n::lookup(&message).to_string();
+        Self { kind, message, status: None, retryable: kind.retryable(), candidates: Vec::new() }
+    }
+
+    /// 本地 Service API 错误
+    pub fn local(context: &str, err: &client::Error) -> Self {
+        let (kind, status) = match err {
+            client::Error::Api { status, .. } => (ErrorKind::from_status(*status), Some(*status)),
+            client::Error::Http(e) if e.is_connect() || e.is_timeout() => (ErrorKind::DaemonUnreachable, None),
+            client::Error::Http(e) if e.is_decode() => (ErrorKind::InvalidResponse, None),
+            client::Error::Http(e) => (e.status().map_or(ErrorKind::Api, |s| ErrorKind::from_status(s.as_u16())), None),
+            client::Error::Json(_) => (ErrorKind::InvalidResponse, None),
+        };
+        Self { status, ..Self::new(kind, format!("{}: {}", i18n::lookup(context), err)) }
+    }
+
+    /// Central API 错误
+    pub fn central(context: &str, err: &central::Error) -> Self {
+        let (kind, status) = match err {
+            central::Error::Api { status, .. } => (ErrorKind::from_status(*status), Some(*status)),
+            central::Error::Http(e) if e.is_connect() || e.is_timeout() => (ErrorKind::CentralUnreachable, None),
+            central::Error::Http(e) if e.is_decode() => (ErrorKind::InvalidResponse, None),
+            central::Error::Http(e) => (e.status().map_or(ErrorKind::Api, |s| ErrorKind::from_status(s.as_u16())), None),
+            central::Error::Json(_) => (ErrorKind::InvalidResponse, None),
+            central::Error::InvalidVersion(_) => (ErrorKind::InvalidParams, None),
+        };
+        Self { status, ..Self::new(kind, format!("{}: {}", i18n::lookup(context), err)) }
+    }
+
+    pub fn central_not_configured() -> Self {
+        Self::new(ErrorKind::CentralNotConfigured, "未配置 Central API Token（ZEROTIER_CENTRAL_TOKEN）")
+    }
+
+    pub fn controller_disabled() -> Self {
+        Self::new(ErrorKind::ControllerDisabled, "本机未启用网络控制器（controller = false），控制器工具不可用")
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidParams, message)
+    }
+}
+
+/// 失败结果：`isError = true`，`content` 为错误信息，`structuredContent` 为 `{"error": {...}}`
+pub fn failure(err: ToolError) -> ToolResult {
+    let text = format!("{} [{}]", err.message, err.kind.as_str());
+    Ok(CallToolResult {
+        content: vec![Content::text(text)],
+        structured_content: Some(serde_json::json!({ "error": err })),
+        is_error: Some(true),
+        meta: None,
+    })
+}
@@ -0,0 +1,164 @@
+//! HTTP 传输（Streamable HTTP 与 SSE）
+
+use super::server::McpServer;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use rmcp::transport::{
+    sse_server::{SseServer, SseServerConfig},
+    streamable_http_server::{session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService},
+};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// HTTP 服务配置
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    bind: SocketAddr,
+    bearer_token: Option<String>,
+    cors_origins: Vec<String>,
+    mcp_path: String,
+    sse_path: String,
+    message_path: String,
+}
+
+impl HttpOptions {
+    /// 创建配置，默认路径：Streamable HTTP 为 `/mcp`，SSE 为 `/sse` + `/message`
+    pub fn new(bind: SocketAddr) -> Self {
+        Self {
+            bind,
+            bearer_token: None,
+            cors_origins: Vec::new(),
+            mcp_path: "/mcp".to_string(),
+            sse_path: "/sse".to_string(),
+            message_path: "/message".to_string(),
+        }
+    }
+
+    /// 要求请求携带 `Authorization: Bearer <token>`
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// 允许跨域访问的来源，`*` 表示任意来源；为空时不启用 CORS
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.cors_origins.push(origin.into());
+        self
+    }
+
+    /// 设置 Streamable HTTP 路径
+    pub fn mcp_path(mut self, path: impl Into<String>) -> Self {
+        self.mcp_path = path.into();
+        self
+    }
+
+    /// 设置 SSE 连接路径与消息路径
+    pub fn sse_paths(mut self, sse_path: impl Into<String>, message_path: impl Into<String>) -> Self {
+        self.sse_path = sse_path.into();
+        self.message_path = message_path.into();
+        self
+    }
+
+    pub fn bind_addr(&self) -> SocketAddr {
+        self.bind
+    }
+
+    fn cors_layer(&self) -> Option<CorsLayer> {
+        if self.cors_origins.is_empty() {
+            return None;
+        }
+        let origin = if self.cors_origins.iter().any(|o| o == "*") {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(self.cors_origins.iter().filter_map(|o| HeaderValue::from_str(o).ok()))
+        };
+        let mcp_headers = [
+            HeaderName::from_static("mcp-session-id"),
+            HeaderName::from_static("mcp-protocol-version"),
+            HeaderName::from_static("last-event-id"),
+        ];
+        Some(
+            CorsLayer::new()
+                .allow_origin(origin)
+                .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+                .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::ACCEPT].into_iter().chain(mcp_headers.clone()).collect::<Vec<_>>())
+                .expose_headers(mcp_headers),
+        )
+    }
+}
+
+/// 校验 Bearer Token（常量时间比较；CORS 层位于外侧，预检请求不会到达这里）
+async fn require_bearer(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|t| bool::from(t.as_bytes().ct_eq(token.as_bytes())));
+    if authorized {
+        next.run(request).await
+    } else {
+        (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, "Bearer")], "unauthorized").into_response()
+    }
+}
+
+impl McpServer {
+    /// 启动 HTTP 服务，同时提供 Streamable HTTP 与 SSE 两种传输，直到收到 Ctrl-C
+    ///
+    /// 每个会话使用独立的服务实例（订阅等会话状态互不影响）。
+    pub async fn serve_http(self, options: HttpOptions) -> Result<(), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind(options.bind).await?;
+        self.serve_http_on(listener, options).await
+    }
+
+    /// 在已绑定的监听器上启动 HTTP 服务，便于调用方在绑定成功后再输出日志
+    pub async fn serve_http_on(self, listener: TcpListener, options: HttpOptions) -> Result<(), Box<dyn std::error::Error>> {
+        let ct = CancellationToken::new();
+
+        let factory = self.clone();
+        let streamable = StreamableHttpService::new(
+            move || Ok(factory.new_session()),
+            Arc::new(LocalSessionManager::default()),
+            StreamableHttpServerConfig::default(),
+        );
+
+        let (sse_server, sse_router) = SseServer::new(SseServerConfig {
+            bind: options.bind,
+            sse_path: options.sse_path.clone(),
+            post_path: options.message_path.clone(),
+            ct: ct.clone(),
+            sse_keep_alive: None,
+        });
+        let factory = self.clone();
+        sse_server.with_service(move || factory.new_session());
+
+        let mut router = Router::new().nest_service(&options.mcp_path, streamable).merge(sse_router);
+        if let Some(token) = &options.bearer_token {
+            let token: Arc<str> = Arc::from(token.as_str());
+            router = router.layer(middleware::from_fn_with_state(token, require_bearer));
+        }
+        if let Some(cors) = options.cors_layer() {
+            router = router.layer(cors);
+        }
+
+        let shutdown = ct.clone();
+        axum::serve(listener, router)
+            .with_graceful_shutdown(async move {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => shutdown.cancel(),
+                    _ = shutdown.cancelled() => {}
+                }
+            })
+            .await?;
+        Ok(())
+    }
+}
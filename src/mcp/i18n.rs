@@ -0,0 +1,637 @@
+//! 工具文本本地化（中文 / English）
+//!
+//! 源码中的文本以中文书写，中文即源语言目录，同时作为查找英文目录的键（类似 gettext）；
+//! 英文目录中缺少的条目回退为中文。模板中的 `{}` 依次填入参数，`{0}`、`{1}` 按位置填入，
+//! 以便译文调整语序。当前语言在每次请求处理时通过 task-local 设置。
+
+use rmcp::model::{Prompt, Tool};
+use serde_json::Value;
+use std::sync::Arc;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 中文（默认）
+    #[default]
+    Zh,
+    /// English
+    En,
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// 接受 `zh`、`zh-CN`、`en`、`en_US.UTF-8` 等写法
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lang = s.trim().to_ascii_lowercase();
+        if lang.starts_with("zh") || lang == "cn" {
+            Ok(Self::Zh)
+        } else if lang.starts_with("en") {
+            Ok(Self::En)
+        } else {
+            Err(format!("unsupported language: {} (expected zh or en)", s))
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Zh => "zh",
+            Self::En => "en",
+        })
+    }
+}
+
+tokio::task_local! {
+    static LOCALE: Locale;
+}
+
+/// 在指定语言下执行
+pub(super) async fn scope<F: Future>(locale: Locale, f: F) -> F::Output {
+    LOCALE.scope(locale, f).await
+}
+
+/// 当前请求的语言，不在请求中时为默认语言
+pub(super) fn current() -> Locale {
+    LOCALE.try_with(|l| *l).unwrap_or_default()
+}
+
+/// 按指定语言翻译
+pub(super) fn translate(locale: Locale, zh: &str) -> &str {
+    match locale {
+        Locale::Zh => zh,
+        Locale::En => english().get(zh).copied().unwrap_or(zh),
+    }
+}
+
+/// 按当前语言翻译
+pub(super) fn lookup(zh: &str) -> &str {
+    translate(current(), zh)
+}
+
+/// 按当前语言翻译模板并填入参数
+pub(super) fn format(zh: &str, args: &[&dyn Display]) -> String {
+    fill(lookup(zh), args)
+}
+
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) if after[..end].bytes().all(|b| b.is_ascii_digit()) => {
+                let index = if end == 0 {
+                    next += 1;
+                    next - 1
+                } else {
+                    after[..end].parse().unwrap_or(usize::MAX)
+                };
+                if let Some(arg) = args.get(index) {
+                    let _ = write!(out, "{}", arg);
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 翻译 JSON Schema 中的 `description`
+pub(super) fn translate_schema(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                match v {
+                    Value::String(s) if key == "description" => *s = lookup(s).to_string(),
+                    _ => translate_schema(v),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(translate_schema),
+        _ => {}
+    }
+}
+
+/// 翻译工具说明以及输入、输出 Schema
+pub(super) fn translate_tool(mut tool: Tool) -> Tool {
+    if current() == Locale::Zh {
+        return tool;
+    }
+    if let Some(description) = &tool.description {
+        tool.description = Some(lookup(description).to_string().into());
+    }
+    let mut input = Value::Object((*tool.input_schema).clone());
+    translate_schema(&mut input);
+    if let Value::Object(input) = input {
+        tool.input_schema = Arc::new(input);
+    }
+    if let Some(output) = &tool.output_schema {
+        let mut output = Value::Object((**output).clone());
+        translate_schema(&mut output);
+        if let Value::Object(output) = output {
+            tool.output_schema = Some(Arc::new(output));
+        }
+    }
+    tool
+}
+
+/// 按当前语言翻译提示词及其参数的说明
+pub(super) fn translate_prompt(mut prompt: Prompt) -> Prompt {
+    if current() == Locale::Zh {
+        return prompt;
+    }
+    if let Some(description) = &prompt.description {
+        prompt.description = Some(lookup(description).to_string());
+    }
+    for argument in prompt.arguments.iter_mut().flatten() {
+        if let Some(description) = &argument.description {
+            argument.description = Some(lookup(description).to_string());
+        }
+    }
+    prompt
+}
+
+/// 翻译文本：`tr!("中文")` 返回 `&str`，`tr!("模板 {}", arg)` 返回 `String`
+macro_rules! tr {
+    ($zh:literal) => {
+        $crate::mcp::i18n::lookup($zh)
+    };
+    ($zh:literal, $($arg:expr),+ $(,)?) => {
+        $crate::mcp::i18n::format($zh, &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+pub(super) use tr;
+
+fn english() -> &'static HashMap<&'static str, &'static str> {
+    static CATALOG: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    CATALOG.get_or_init(|| EN.iter().copied().collect())
+}
+
+/// 英文目录
+const EN: &[(&str, &str)] = &[
+    // 服务信息
+    ("ZeroTier SDK MCP Server - 管理本地和云端 ZeroTier 网络", "ZeroTier SDK MCP Server - manage local and Central ZeroTier networks"),
+    // 工具说明
+    ("获取本地 ZeroTier 节点状态", "Get the local ZeroTier node status"),
+    ("列出可用的 profile（Central 账号与本地/远程节点），其他工具可通过 profile 参数选用", "List available profiles (Central accounts and local/remote nodes); other tools select one with the profile parameter"),
+    ("列出已加入的 ZeroTier 网络", "List joined ZeroTier networks"),
+    ("加入 ZeroTier 网络", "Join a ZeroTier network"),
+    ("离开 ZeroTier 网络", "Leave a ZeroTier network"),
+    ("列出所有 ZeroTier Peers", "List all ZeroTier peers"),
+    ("获取已加入网络的完整信息：状态、设备名、MTU、配置版本、地址、路由、DNS 与本机设置", "Get full details of a joined network: status, device name, MTU, netconf revision, addresses, routes, DNS and local settings"),
+    ("修改本机对已加入网络的设置：allowDNS、allowDefault、allowGlobal、allowManaged", "Change the local settings of a joined network: allowDNS, allowDefault, allowGlobal, allowManaged"),
+    ("获取单个 Peer 的详情与各条路径，附带距上次收发的时长", "Get one peer with its paths, including time since the last receive and send"),
+    ("列出云端 ZeroTier 网络", "List ZeroTier Central networks"),
+    ("列出网络成员", "List network members"),
+    ("授权网络成员", "Authorize a network member"),
+    ("授权网络成员并指定自定义 IP 地址", "Authorize a network member and assign a custom IP address"),
+    ("取消成员授权", "Deauthorize a network member"),
+    ("获取本机自托管控制器状态", "Get the status of the local self-hosted controller"),
+    ("列出本机控制器管理的网络", "List networks managed by the local controller"),
+    ("在本机控制器上创建网络，可通过 CIDR 自动配置路由与 IP 分配池", "Create a network on the local controller; a CIDR configures the managed route and IP assignment pool"),
+    ("更新本机控制器网络的名称、私有标志、MTU 或网段", "Update the name, private flag, MTU or subnet of a local controller network"),
+    ("删除本机控制器上的网络", "Delete a network on the local controller"),
+    ("列出本机控制器网络的成员", "List members of a local controller network"),
+    ("授权本机控制器网络成员，可同时指定 IP 地址", "Authorize a local controller network member, optionally assigning IP addresses"),
+    ("取消本机控制器网络成员的授权", "Deauthorize a local controller network member"),
+    ("从本机控制器网络中删除成员", "Delete a member from a local controller network"),
+    ("{}（需二次确认：首次调用返回预览与 confirm_token）", "{} (requires confirmation: the first call returns a preview and a confirm_token)"),
+    // 参数说明
+    ("网络 ID（16位十六进制）", "Network ID (16 hex digits)"),
+    ("网络 ID", "Network ID"),
+    ("成员 ID", "Member ID"),
+    ("自定义 IP 地址（如 10.147.20.100）", "Custom IP address (e.g. 10.147.20.100)"),
+    ("成员名称（可选）", "Member name (optional)"),
+    ("网络名称", "Network name"),
+    ("网段 CIDR（如 10.147.20.0/24），自动生成受管路由与 IP 分配池（可选）", "Subnet CIDR (e.g. 10.147.20.0/24); generates the managed route and IP assignment pool (optional)"),
+    ("是否为私有网络，默认 true（可选）", "Whether the network is private, default true (optional)"),
+    ("新名称（可选）", "New name (optional)"),
+    ("新网段 CIDR，将替换路由与 IP 分配池（可选）", "New subnet CIDR; replaces the routes and IP assignment pools (optional)"),
+    ("是否为私有网络（可选）", "Whether the network is private (optional)"),
+    ("MTU（可选）", "MTU (optional)"),
+    ("指定 IP 地址列表（可选，如 [\"10.147.20.100\"]）", "IP addresses to assign (optional, e.g. [\"10.147.20.100\"])"),
+    ("确认令牌：首次调用不带此参数会返回预览与令牌，确认无误后以相同参数携带令牌再次调用才会执行", "Confirmation token: a call without it returns a preview and a token; call again with the same arguments plus the token to execute"),
+    ("使用的 profile（账号/节点），省略时使用默认 profile \"{}\"；可用 zt_profiles 查看", "Profile (account/node) to use; defaults to \"{}\" when omitted. See zt_profiles"),
+    ("使用的 profile（账号/节点），省略时使用默认客户端；可用 zt_profiles 查看", "Profile (account/node) to use; the default clients are used when omitted. See zt_profiles"),
+    // 输出类型说明
+    ("DNS 配置", "DNS configuration"),
+    ("IP 分配模式", "IP assignment mode"),
+    ("IP 分配池", "IP assignment pool"),
+    ("SSO 配置", "SSO configuration"),
+    ("路由配置", "Route configuration"),
+    ("网络信息", "Network information"),
+    ("网络配置", "Network configuration"),
+    ("网络成员", "Network member"),
+    ("成员配置", "Member configuration"),
+    ("本地网络信息", "Local network information"),
+    ("节点状态", "Node status"),
+    ("节点信息", "Peer information"),
+    ("节点路径", "Peer path"),
+    ("受管路由", "Managed routes"),
+    ("Peer 详情：路径附带距上次收发的时长", "Peer details; paths include time since the last receive and send"),
+    ("Peer 路径，时长按节点时钟计算", "Peer path; ages are computed from the node clock"),
+    ("距上次接收的毫秒数，从未接收时为空", "Milliseconds since the last receive; empty if nothing was ever received"),
+    ("距上次发送的毫秒数，从未发送时为空", "Milliseconds since the last send; empty if nothing was ever sent"),
+    ("控制器状态", "Controller status"),
+    ("控制器成员", "Controller member"),
+    ("控制器网络配置", "Controller network configuration"),
+    ("编译后的流规则", "Compiled flow rules"),
+    ("由规则源编译得到的流规则", "Flow rules compiled from the rules source"),
+    ("规则源（控制器仅存储，不编译）", "Rules source (stored by the controller, not compiled)"),
+    ("离开/删除网络的结果", "Result of leaving/deleting a network"),
+    ("删除成员的结果", "Result of deleting a member"),
+    ("`zt_profiles` 输出", "`zt_profiles` output"),
+    ("profile 信息（不含 Token）", "Profile information (tokens excluded)"),
+    ("是否为默认 profile", "Whether this is the default profile"),
+    ("未配置 Central 时为空", "Empty when Central is not configured"),
+    ("操作预览", "Operation preview"),
+    ("恒为 true，表示操作尚未执行", "Always true: the operation has not been executed"),
+    ("将要执行的操作", "Operation to be executed"),
+    ("受影响的 IP", "Affected IP addresses"),
+    ("目标是否为本机节点", "Whether the target is this node"),
+    ("删除网络或批量操作时受影响的成员数", "Number of affected members when deleting a network or running a bulk operation"),
+    ("预览信息获取失败等提示（如成员不存在，可能是 ID 有误）", "Warnings such as failed lookups (a missing member may indicate a wrong ID)"),
+    ("以相同参数再次调用时携带此令牌以执行操作", "Pass this token with the same arguments to execute the operation"),
+    // 资源
+    ("本地节点状态", "Local node status"),
+    ("已加入的网络", "Joined networks"),
+    ("本地节点的 Peers", "Peers of the local node"),
+    ("云端网络列表", "Central networks"),
+    ("已加入网络的本地状态", "Local state of a joined network"),
+    ("云端网络配置", "Central network configuration"),
+    ("云端网络成员列表", "Central network members"),
+    // 结果
+    ("节点 {} ({})，{}", "Node {} ({}), {}"),
+    ("在线", "online"),
+    ("离线", "offline"),
+    ("未配置 profile，所有工具使用默认客户端", "No profiles configured; all tools use the default clients"),
+    ("{}（默认）", "{} (default)"),
+    ("共 {} 个 profile: {}", "{} profiles: {}"),
+    ("已加入 {} 个网络", "Joined {} networks"),
+    ("已加入网络: {} ({})", "Joined network: {} ({})"),
+    ("已离开网络: {}", "Left network: {}"),
+    ("共 {} 个 Peers", "{} peers"),
+    ("云端共 {} 个网络", "{} networks on Central"),
+    ("网络 {} 共 {} 个成员，已授权 {} 个", "Network {} has {} members, {} authorized"),
+    ("已授权成员: {} ({})", "Authorized member: {} ({})"),
+    ("已授权成员: {} ({})，IP: {}", "Authorized member: {} ({}), IP: {}"),
+    ("已取消授权: {} ({})", "Deauthorized: {} ({})"),
+    ("控制器{}，API 版本 {}", "Controller {}, API version {}"),
+    ("已启用", "enabled"),
+    ("未启用", "disabled"),
+    ("控制器共 {} 个网络", "Controller has {} networks"),
+    ("已创建网络: {} ({})", "Created network: {} ({})"),
+    ("已更新网络: {} ({})", "Updated network: {} ({})"),
+    ("已删除网络: {}", "Deleted network: {}"),
+    ("已授权成员: {}，IP: {}", "Authorized member: {}, IP: {}"),
+    ("已取消授权: {}", "Deauthorized: {}"),
+    ("已删除成员: {}", "Deleted member: {}"),
+    // 错误
+    ("获取状态失败", "Failed to get status"),
+    ("获取网络失败", "Failed to get network"),
+    ("获取 Peers 失败", "Failed to get peers"),
+    ("获取 Peer 失败", "Failed to get the peer"),
+    ("更新网络设置失败", "Failed to update the network settings"),
+    ("获取成员失败", "Failed to get members"),
+    ("加入网络失败", "Failed to join network"),
+    ("离开网络失败", "Failed to leave network"),
+    ("授权失败", "Failed to authorize"),
+    ("取消授权失败", "Failed to deauthorize"),
+    ("获取控制器状态失败", "Failed to get controller status"),
+    ("获取网络 {} 详情失败", "Failed to get details of network {}"),
+    ("获取成员 {} 详情失败", "Failed to get details of member {}"),
+    ("网段配置无效: {}", "Invalid subnet: {}"),
+    ("获取节点地址失败", "Failed to get node address"),
+    ("创建网络失败", "Failed to create network"),
+    ("更新网络失败", "Failed to update network"),
+    ("删除网络失败", "Failed to delete network"),
+    ("删除成员失败", "Failed to delete member"),
+    ("未配置 Central API Token（ZEROTIER_CENTRAL_TOKEN）", "Central API token is not configured (ZEROTIER_CENTRAL_TOKEN)"),
+    ("本机未启用网络控制器（controller = false），控制器工具不可用", "The local network controller is not enabled (controller = false); controller tools are unavailable"),
+    ("写入审计日志 {} 失败: {}", "Failed to write audit log {}: {}"),
+    // 访问控制与策略
+    ("工具 {} 已被禁用：匹配禁止列表中的 \"{}\"", "Tool {} is disabled: matches \"{}\" in the deny list"),
+    ("工具 {} 已被禁用：不在允许列表中", "Tool {} is disabled: not in the allow list"),
+    ("工具 {} 已被禁用：服务处于只读模式", "Tool {} is disabled: the server is in read-only mode"),
+    ("工具 {} 已被禁用：Central API Token 处于只读模式（read_only_mode），不允许修改云端数据", "Tool {} is disabled: the Central API token is in read-only mode (read_only_mode) and cannot modify Central data"),
+    ("网络 {}（{}）的 {} 操作", "{2} on network {0} ({1})"),
+    ("网络 {} 的 {} 操作", "{1} on network {0}"),
+    ("{} 操作", "{}"),
+    ("networks[{}]（{}）", "networks[{}] ({})"),
+    ("[default] ", "[default]"),
+    ("{}被策略拒绝：规则 {}的 deny 包含 \"{}\"", "{} denied by policy: the deny list of rule {} contains \"{}\""),
+    ("{}被策略拒绝：规则 {}不允许任何写操作", "{} denied by policy: rule {} allows no write operations"),
+    ("{}被策略拒绝：规则 {}只允许 {}", "{} denied by policy: rule {} only allows {}"),
+    ("{}被策略拒绝：规则 networks[{}]（{}）按名称匹配，但无法获取网络名称", "{} denied by policy: rule networks[{}] ({}) matches by name, but the network name could not be retrieved"),
+    ("网络策略没有覆盖写操作 {}，按拒绝处理", "The network policy does not cover the write operation {}; denied"),
+    ("profile 必须是字符串", "profile must be a string"),
+    ("未知的 profile \"{}\"：未配置任何 profile", "Unknown profile \"{}\": no profiles are configured"),
+    ("未知的 profile \"{}\"，可用: {}", "Unknown profile \"{}\", available: {}"),
+    // 本地网络与 Peer
+    ("是否接受网络下发的 DNS 配置（可选）", "Whether to accept DNS configuration pushed by the network (optional)"),
+    ("是否允许网络覆盖系统默认路由（可选）", "Whether the network may override the system default route (optional)"),
+    ("是否允许受管 IP 与路由覆盖公网地址段（可选）", "Whether managed IPs and routes may overlap public address space (optional)"),
+    ("是否接受网络分配的受管 IP 与路由（可选）", "Whether to accept managed IPs and routes assigned by the network (optional)"),
+    ("Peer 地址（10位十六进制节点 ID）", "Peer address (10-digit hex node ID)"),
+    ("开", "on"),
+    ("关", "off"),
+    ("网络 {} ({})，状态 {}，类型 {}", "Network {} ({}), status {}, type {}"),
+    ("设备 {}，MAC {}，MTU {}，配置版本 {}", "Device {}, MAC {}, MTU {}, netconf revision {}"),
+    ("地址: {}", "Addresses: {}"),
+    ("已更新网络设置:\n{}", "Updated network settings:\n{}"),
+    ("从未", "never"),
+    ("{} 秒前", "{} s ago"),
+    ("{} 分钟前", "{} min ago"),
+    ("{} 小时前", "{} h ago"),
+    ("Peer {} ({}, {})，延迟 {} ms", "Peer {} ({}, {}), latency {} ms"),
+    ("无直连路径", "No direct paths"),
+    ("活跃", "active"),
+    ("首选", "preferred"),
+    ("已过期", "expired"),
+    ("{} [{}]，接收 {}，发送 {}", "{} [{}], received {}, sent {}"),
+    // Central 网络管理
+    ("在 Central 创建网络，可通过 CIDR 自动配置路由与 IP 分配池", "Create a network on Central; a CIDR sets up the managed route and IP assignment pool automatically"),
+    ("更新 Central 网络的名称、描述、私有标志、MTU、路由、IP 分配池或 DNS；路由与分配池整体替换", "Update the name, description, private flag, MTU, routes, IP assignment pools or DNS of a Central network; routes and pools are replaced as a whole"),
+    ("替换 Central 网络的流规则源码（ZeroTier 规则语言），由 Central 编译生效", "Replace the flow rules source (ZeroTier rules language) of a Central network; Central compiles and applies it"),
+    ("删除 Central 网络及其全部成员", "Delete a Central network and all of its members"),
+    ("网络描述（可选）", "Network description (optional)"),
+    ("新描述（可选）", "New description (optional)"),
+    ("目标网段 CIDR", "Target CIDR"),
+    ("网关地址，省略表示受管路由（可选）", "Gateway address; omit for a managed route (optional)"),
+    ("起始 IP", "First IP"),
+    ("结束 IP", "Last IP"),
+    ("MTU，1280-10000（可选）", "MTU, 1280-10000 (optional)"),
+    ("路由列表，将替换现有路由（可选）", "Routes; replaces the existing routes (optional)"),
+    ("IP 分配池列表，将替换现有分配池（可选）", "IP assignment pools; replaces the existing pools (optional)"),
+    ("DNS 搜索域，须与 dns_servers 同时提供；两者均为空时清除 DNS（可选）", "DNS search domain, must be given together with dns_servers; both empty clears DNS (optional)"),
+    ("DNS 服务器地址列表（可选）", "DNS server addresses (optional)"),
+    ("流规则源码（ZeroTier 规则语言），将替换现有规则", "Flow rules source (ZeroTier rules language); replaces the existing rules"),
+    ("无效的 IP 地址: {}", "Invalid IP address: {}"),
+    ("无效的网段 CIDR: {}", "Invalid CIDR: {}"),
+    ("网络名称不能为空", "The network name must not be empty"),
+    ("路由 {} 的网关 {} 与目标地址族不一致", "The gateway {1} of route {0} is not in the same address family as the target"),
+    ("无效的 IP 分配池: {} - {}", "Invalid IP assignment pool: {} - {}"),
+    ("dns_domain 与 dns_servers 须同时为空或同时非空", "dns_domain and dns_servers must both be empty or both be non-empty"),
+    ("dns_domain 与 dns_servers 须同时提供", "dns_domain and dns_servers must be given together"),
+    ("MTU {} 超出范围（{}-{}）", "MTU {} is out of range ({}-{})"),
+    ("未指定任何要更新的字段", "No fields to update were given"),
+    ("流规则源码不能为空", "The rules source must not be empty"),
+    ("网络 {} ({})", "Network {} ({})"),
+    ("网络 {}", "Network {}"),
+    ("私有: {}，MTU: {}", "Private: {}, MTU: {}"),
+    ("路由: {}", "Routes: {}"),
+    ("IP 分配池: {}", "IP pools: {}"),
+    ("描述: {}", "Description: {}"),
+    ("已创建网络:\n{}", "Created network:\n{}"),
+    ("已更新网络:\n{}", "Updated network:\n{}"),
+    ("已更新流规则，编译后共 {} 条规则:\n{}", "Updated flow rules, {} compiled rules:\n{}"),
+    ("更新流规则失败", "Failed to update flow rules"),
+    ("修改 Central 网络成员的名称、描述、桥接或自动分配 IP 设置，不改变授权状态", "Change the name, description, bridging or IP auto-assign setting of a Central network member without changing its authorization"),
+    ("设置或清除 Central 网络成员的指定 IP，不改变授权状态", "Set or clear the assigned IPs of a Central network member without changing its authorization"),
+    ("从 Central 网络中删除成员，设备再次加入时需重新授权", "Delete a member from a Central network; the device must be authorized again if it rejoins"),
+    ("是否允许作为以太网桥（可选）", "Whether the member may act as an Ethernet bridge (optional)"),
+    ("是否禁止从分配池自动分配 IP（可选）", "Whether automatic IP assignment from the pools is disabled (optional)"),
+    ("IP 地址列表，将替换现有地址；空列表表示清除", "IP addresses; replaces the existing ones, an empty list clears them"),
+    ("成员 {} ({})", "Member {} ({})"),
+    ("已授权: {}，桥接: {}，自动分配 IP: {}", "Authorized: {}, bridge: {}, auto-assign IPs: {}"),
+    ("已更新成员:\n{}", "Updated member:\n{}"),
+    ("更新成员失败", "Failed to update the member"),
+    ("已设置成员 IP:\n{}", "Set member IPs:\n{}"),
+    ("设置成员 IP 失败", "Failed to set the member IPs"),
+    ("已删除成员: {}（网络 {}）", "Deleted member: {} (network {})"),
+
+    // 成员查询
+    ("按条件查询 Central 网络成员：授权状态、名称/描述正则、IP 网段、最后在线时间、客户端版本范围、物理地址、桥接与标签，支持排序与分页，适合成员较多的网络", "Query Central network members by authorization state, name/description regex, IP subnet, last seen time, client version range, physical address, bridge flag and tags, with sorting and paging; suited to networks with many members"),
+    ("名称正则表达式，如 ^ci-（可选）", "Name regular expression, e.g. ^ci- (optional)"),
+    ("描述正则表达式（可选）", "Description regular expression (optional)"),
+    ("指定 IP 所在网段 CIDR，如 10.147.20.0/24（可选）", "Subnet CIDR of the assigned IPs, e.g. 10.147.20.0/24 (optional)"),
+    ("只选择超过多少分钟未在线的成员，含从未在线的成员（可选）", "Only select members not seen for at least this many minutes, including members never seen (optional)"),
+    ("客户端版本下限，如 1.12（可选）", "Minimum client version, e.g. 1.12 (optional)"),
+    ("客户端版本上限（含），如 1.14.2（可选）", "Maximum client version (inclusive), e.g. 1.14.2 (optional)"),
+    ("物理地址通配符，如 203.0.113.*（可选）", "Physical address wildcard pattern, e.g. 203.0.113.* (optional)"),
+    ("按是否允许作为以太网桥筛选（可选）", "Filter by whether the member may act as an Ethernet bridge (optional)"),
+    ("只选择带有该标签 ID 的成员（可选）", "Only select members that have this tag ID (optional)"),
+    ("与 tag_id 一起使用，要求标签值相同（可选）", "Used with tag_id to require this tag value (optional)"),
+    ("排序字段：name、id、last_seen、client_version 或 ip，默认 id（可选）", "Sort field: name, id, last_seen, client_version or ip; default id (optional)"),
+    ("是否降序（可选）", "Sort in descending order (optional)"),
+    ("跳过前多少个结果，用于分页（可选）", "Number of results to skip, for paging (optional)"),
+    ("最多返回的成员数，默认 50，最大 500（可选）", "Maximum number of members to return, default 50, at most 500 (optional)"),
+    ("无效的正则表达式 {}: {}", "Invalid regular expression {}: {}"),
+    ("无效的版本号: {}（应为 1.12 或 1.12.2 的形式）", "Invalid version: {} (expected a form like 1.12 or 1.12.2)"),
+    ("tag_value 需要与 tag_id 一起使用", "tag_value must be used together with tag_id"),
+    ("未知的排序字段 \"{}\"，可用: name、id、last_seen、client_version、ip", "Unknown sort field \"{}\"; available: name, id, last_seen, client_version, ip"),
+    ("limit {} 超出范围（1-{}）", "limit {} is out of range (1-{})"),
+    ("网络 {} 中匹配 {} 个成员，显示 {} 个", "{1} members match in network {0}, showing {2}"),
+    ("网络 {} 中匹配 {} 个成员，跳过 {} 个后显示 {} 个", "{1} members match in network {0}, showing {3} after skipping {2}"),
+    ("已授权", "authorized"),
+    ("未授权", "unauthorized"),
+    ("查询结果", "Query result"),
+    ("匹配条件的成员总数（截取前）", "Total number of matching members (before paging)"),
+    ("排序并截取后的成员", "Members after sorting and paging"),
+
+    // 成员批量操作
+    ("按条件筛选 Central 网络成员，批量授权、取消授权、设置标签、删除或按模板重命名；以有限并发执行并逐个报告结果，部分失败不影响其余成员。请求携带 progressToken 时发送进度通知", "Select Central network members by filter and authorize, deauthorize, set tags, delete or rename them from a template in bulk; runs with bounded concurrency and reports each member, so partial failures do not affect the rest. Sends progress notifications when the request carries a progressToken"),
+    ("标签 ID", "Tag ID"),
+    ("标签值", "Tag value"),
+    ("操作：authorize、deauthorize、set_tags、delete 或 rename", "Action: authorize, deauthorize, set_tags, delete or rename"),
+    ("只选择这些成员 ID（可选）", "Only select these member IDs (optional)"),
+    ("按授权状态筛选（可选）", "Filter by authorization state (optional)"),
+    ("名称通配符，* 匹配任意字符，如 ci-*（可选）", "Name wildcard pattern, * matches anything, e.g. ci-* (optional)"),
+    ("只选择最近多少分钟内在线过的成员（可选）", "Only select members seen within this many minutes (optional)"),
+    ("set_tags 使用的标签列表，将替换现有标签", "Tags for set_tags; replaces the existing tags"),
+    ("rename 使用的名称模板，支持 {name}（原名称）、{id}（成员 ID）、{n}（序号）", "Name template for rename; supports {name} (current name), {id} (member ID) and {n} (sequence number)"),
+    ("最大并发数，1-16，默认 4（可选）", "Maximum concurrency, 1-16, default 4 (optional)"),
+    ("set_tags 需要提供 tags", "set_tags requires tags"),
+    ("rename 需要提供非空的 name_template", "rename requires a non-empty name_template"),
+    ("未知的批量操作 \"{}\"，可用: authorize、deauthorize、set_tags、delete、rename", "Unknown bulk action \"{}\"; available: authorize, deauthorize, set_tags, delete, rename"),
+    ("请至少指定一个筛选条件（member_ids、authorized、name_pattern 或 seen_within_minutes）", "Specify at least one filter (member_ids, authorized, name_pattern or seen_within_minutes)"),
+    ("并发数 {} 超出范围（{}-{}）", "Concurrency {} is out of range ({}-{})"),
+    ("没有匹配筛选条件的成员", "No members match the filter"),
+    ("对 {} 个成员执行 {}：成功 {}，失败 {}", "Ran {1} on {0} members: {2} succeeded, {3} failed"),
+    ("成功", "ok"),
+    ("失败", "failed"),
+    ("失败: {}", "failed: {}"),
+    ("批量操作报告，部分失败不影响其余成员", "Bulk operation report; partial failures do not affect the other members"),
+    ("操作名", "Action name"),
+    ("匹配筛选条件的成员数", "Number of members matching the filter"),
+    ("按筛选结果顺序排列的逐个成员结果", "Per-member results in selection order"),
+    ("单个成员的执行结果", "Result for a single member"),
+    ("执行前的成员名称", "Member name before the operation"),
+    ("重命名后的名称", "Name after renaming"),
+    ("失败原因", "Failure reason"),
+    // 二次确认
+    ("确认令牌无效或已使用，请不带 confirm_token 重新调用以获取预览", "The confirmation token is invalid or already used; call again without confirm_token to get a new preview"),
+    ("确认令牌已过期，请不带 confirm_token 重新调用以获取预览", "The confirmation token has expired; call again without confirm_token to get a new preview"),
+    ("确认令牌属于 {} 的另一组参数，与本次调用不符，请重新获取预览", "The confirmation token was issued for {} with different arguments; request a new preview"),
+    ("confirm_token 必须是字符串", "confirm_token must be a string"),
+    ("无法获取本机节点 ID: {}", "Unable to get the local node ID: {}"),
+    ("本机离开网络 {}", "This node leaves network {}"),
+    ("本机未加入该网络或无法读取: {}", "This node has not joined the network, or it cannot be read: {}"),
+    ("在 Central 取消授权成员 {}（网络 {}）", "Deauthorize member {} on Central (network {})"),
+    ("在本机控制器取消授权成员 {}（网络 {}）", "Deauthorize member {} on the local controller (network {})"),
+    ("从本机控制器删除成员 {}（网络 {}）", "Delete member {} from the local controller (network {})"),
+    ("从 Central 删除成员 {}（网络 {}）", "Delete member {} from Central (network {})"),
+    ("在 Central 删除网络 {} 及其全部成员", "Delete network {} and all of its members on Central"),
+    ("在 Central 对网络 {} 中匹配的成员批量执行 {}", "Run {1} in bulk on the matching members of network {0} on Central"),
+    ("从本机控制器删除网络 {} 及其全部成员", "Delete network {} and all of its members from the local controller"),
+    ("执行 {}", "Run {}"),
+    ("无法读取网络: {}", "Unable to read the network: {}"),
+    ("无法读取成员: {}", "Unable to read the members: {}"),
+    ("成员不存在或无法读取（请核对 ID）: {}", "Member not found or unreadable (check the ID): {}"),
+    ("网络不存在或无法读取（请核对 ID）: {}", "Network not found or unreadable (check the ID): {}"),
+    ("尚未执行，需要确认：{}", "Not executed yet, confirmation required: {}"),
+    ("网络名称: {}", "Network name: {}"),
+    ("成员名称: {}", "Member name: {}"),
+    ("当前已授权: {}", "Currently authorized: {}"),
+    ("是", "yes"),
+    ("否", "no"),
+    ("成员数: {}", "Members: {}"),
+    ("警告: 目标是本机节点，执行后本机可能失去网络连接", "Warning: the target is this node; it may lose network connectivity"),
+    ("警告: {}", "Warning: {}"),
+    ("确认无误后以相同参数并附加 confirm_token = \"{}\" 再次调用（{} 秒内有效）", "If this is correct, call again with the same arguments plus confirm_token = \"{}\" (valid for {} seconds)"),
+
+    // 演练模式
+    ("{}（演练模式：不执行写操作，返回预期变更）", "{} (dry-run mode: nothing is written; returns the intended changes)"),
+    ("演练模式，未执行写操作", "Dry run, nothing was written"),
+    ("没有需要发送的写请求", "No write requests would be sent"),
+    ("演练结果", "Dry-run result"),
+    ("恒为 true，表示写请求未发送", "Always true: the write request was not sent"),
+    ("本次调用中未发送的写请求及预期变更，按发送顺序排列", "Write requests held back during this call and their intended changes, in send order"),
+    ("演练模式下未发送的写请求", "A write request held back in dry-run mode"),
+    ("HTTP 方法（POST / DELETE）", "HTTP method (POST / DELETE)"),
+    ("API 路径", "API path"),
+    ("将要发送的请求体", "Request body that would be sent"),
+    ("当前状态，资源尚不存在时为空", "Current state; null if the resource does not exist yet"),
+    ("写入后的预期状态，删除时为空", "Expected state after the write; null for deletions"),
+    ("将发生变化的字段（嵌套字段以 `.` 连接）", "Fields that would change (nested fields joined with `.`)"),
+    ("单个字段的变更", "A single field change"),
+
+    // 名称解析
+    ("{}；{}", "{}; {}"),
+    ("也可填写网络名称", "a network name is also accepted"),
+    ("也可填写成员名称", "a member name is also accepted"),
+    ("网络", "network"),
+    ("成员", "member"),
+    ("名称 \"{}\" 匹配到 {} 个{}，请从 candidates 中选择 ID 后重试", "Name \"{}\" matches {} {}s; pick an ID from candidates and retry"),
+    ("未找到名称为 \"{}\" 的{}", "No {1} named \"{0}\" found"),
+    ("名称 \"{}\" 没有精确匹配的{}，模糊匹配到 {} 个；修改操作不按模糊匹配执行，请从 candidates 中选择 ID 后重试", "Name \"{}\" has no exact {} match and fuzzily matches {}; modifying tools do not act on fuzzy matches, pick an ID from candidates and retry"),
+    ("名称已解析为 ID：{}", "Names resolved to IDs: {}"),
+    ("获取网络列表失败", "Failed to list networks"),
+
+    // 会话默认值
+    ("设置本会话的默认网络、profile 与结果详细程度，之后的工具可省略 network_id 与 profile；不带参数调用时返回当前默认值", "Set this session's default network, profile and result verbosity so later tools can omit network_id and profile; call without arguments to show the current defaults"),
+    ("默认网络 ID 或名称，之后的工具可省略 network_id（可选）", "Default network ID or name; later tools may omit network_id (optional)"),
+    ("默认 profile，之后的工具可省略 profile（可选）", "Default profile; later tools may omit profile (optional)"),
+    ("结果文本的详细程度：brief 只保留第一行，normal 完整摘要，full 附加结构化结果 JSON（可选）", "Verbosity of result text: brief keeps the first line, normal the full summary, full appends the structured result as JSON (optional)"),
+    ("为 true 时清除本会话的所有默认值（可选）", "Clear all defaults of this session when true (optional)"),
+    ("工具结果文本的详细程度", "Verbosity of tool result text"),
+    ("只保留摘要的第一行", "Keep only the first line of the summary"),
+    ("完整摘要（默认）", "Full summary (default)"),
+    ("摘要之后附加结构化结果的 JSON，供不读取 structuredContent 的客户端使用", "Append the structured result as JSON after the summary, for clients that ignore structuredContent"),
+    ("当前会话的默认值", "Defaults of the current session"),
+    ("默认 profile，未设置时使用服务的默认 profile", "Default profile; the server's default profile is used when unset"),
+    ("默认网络 ID", "Default network ID"),
+    ("默认网络名称", "Default network name"),
+    ("默认 profile {}", "default profile {}"),
+    ("默认网络 {} ({})", "default network {} ({})"),
+    ("默认网络 {}", "default network {}"),
+    ("详细程度 {}", "verbosity {}"),
+    ("当前会话: {}", "Current session: {}"),
+    ("{}；省略时使用会话默认网络 {}", "{}; defaults to the session network {} when omitted"),
+    // 提示词
+    ("引导新设备加入网络：核对网络配置、授权成员并分配 IP", "Guide a new device onto a network: check the network configuration, authorize the member and assign an IP"),
+    ("排查与某个节点的连通性：检查路径、延迟、授权与网络状态", "Troubleshoot connectivity with a node: check paths, latency, authorization and network status"),
+    ("审计网络安全：访问控制、成员授权、桥接与流规则", "Audit network security: access control, member authorization, bridging and flow rules"),
+    ("根据访问策略为网络编写 ZeroTier 流规则（rules source）", "Write ZeroTier flow rules (rules source) for a network from an access policy"),
+    ("新设备的节点 ID（10位十六进制）", "Node ID of the new device (10 hex digits)"),
+    ("设备名称（可选）", "Device name (optional)"),
+    ("对端节点 ID（10位十六进制）", "Node ID of the peer (10 hex digits)"),
+    ("双方所在的网络 ID（可选）", "ID of the network both nodes are on (optional)"),
+    ("用自然语言描述的访问策略，如「只允许 ops 标签的设备访问 22 端口」", "Access policy in natural language, e.g. \"only devices tagged ops may reach port 22\""),
+    ("（获取失败: {}）", "(failed to fetch: {})"),
+    ("Central 网络配置", "Central network configuration"),
+    ("Central 网络成员", "Central network members"),
+    ("Central 成员记录", "Central member record"),
+    ("本机节点状态", "Local node status"),
+    ("对端 Peer 信息", "Peer information"),
+    ("本机网络状态", "Local network status"),
+    ("请帮我把节点 `{}` 接入 ZeroTier 网络 `{}`{}。\n\n", "Please help me add node `{}` to ZeroTier network `{}`{}.\n\n"),
+    ("，设备名称为「{}」", ", with device name \"{}\""),
+    (
+        "步骤：\n\
+         1. 根据下方网络配置确认 IP 分配池与路由，选出一个未被占用的地址；\n\
+         2. 若成员记录不存在，提示我在新设备上执行 `zerotier-cli join <网络 ID>`，然后用 `zt_central_members` 确认其出现；\n\
+         3. 用 `zt_central_authorize_with_ip` 授权该成员并设置名称与 IP；\n\
+         4. 再次读取成员记录，确认已授权且 IP 生效。\n\n",
+        "Steps:\n\
+         1. Check the IP assignment pools and routes in the network configuration below and pick an unused address;\n\
+         2. If the member record does not exist, ask me to run `zerotier-cli join <network ID>` on the new device, then confirm it appears with `zt_central_members`;\n\
+         3. Authorize the member and set its name and IP with `zt_central_authorize_with_ip`;\n\
+         4. Read the member record again and confirm it is authorized and the IP is in effect.\n\n",
+    ),
+    ("将 {} 接入网络 {}", "Add {} to network {}"),
+    ("本机无法与节点 `{}` 正常通信，请帮我排查原因。\n\n", "This machine cannot communicate with node `{}` properly. Please help me find out why.\n\n"),
+    (
+        "请依次检查：\n\
+         1. 本机是否在线、是否处于 TCP 回退（tcpFallbackActive）；\n\
+         2. 对端是否出现在 Peers 中，是否存在活跃且未过期的直连路径，还是只能经由 PLANET/MOON 中继（role / paths）；\n\
+         3. 延迟是否异常（latency 为 -1 表示不可达）；\n\
+         4. 对端在网络中是否已授权、IP 是否已分配、最后在线时间是否过久；\n\
+         5. 本机加入的网络状态是否为 OK。\n\
+         最后给出最可能的原因和具体修复步骤（如防火墙放行 UDP 9993、重新授权等）。\n\n",
+        "Check in order:\n\
+         1. Whether this machine is online and whether it is in TCP fallback (tcpFallbackActive);\n\
+         2. Whether the peer appears in Peers, and whether it has an active, unexpired direct path or is only relayed via PLANET/MOON (role / paths);\n\
+         3. Whether the latency is abnormal (a latency of -1 means unreachable);\n\
+         4. Whether the peer is authorized on the network, has an IP assigned, and was last online recently;\n\
+         5. Whether the status of the networks this machine has joined is OK.\n\
+         Finally, give the most likely cause and concrete fixes (e.g. allow UDP 9993 through the firewall, re-authorize).\n\n",
+    ),
+    ("排查与 {} 的连通性", "Troubleshoot connectivity with {}"),
+    ("请对 ZeroTier 网络 `{}` 做一次安全审计。\n\n", "Please perform a security audit of ZeroTier network `{}`.\n\n"),
+    (
+        "重点关注：\n\
+         1. 网络是否为私有（private = false 时任何人都可加入）；\n\
+         2. 已授权但长期未在线、名称为空或来源不明的成员；\n\
+         3. 开启 activeBridge 的成员及其必要性；\n\
+         4. 流规则是否过于宽松（如仅有 `accept;`），是否使用了标签/能力做最小权限；\n\
+         5. DNS、路由（尤其默认路由 0.0.0.0/0）与 SSO 配置是否合理。\n\
+         请按风险高低列出问题，并给出可以直接执行的修复建议（对应的 MCP 工具或规则片段）。\n\n",
+        "Focus on:\n\
+         1. Whether the network is private (anyone can join when private = false);\n\
+         2. Members that are authorized but have been offline for a long time, have no name, or are of unknown origin;\n\
+         3. Members with activeBridge enabled and whether they need it;\n\
+         4. Whether the flow rules are too permissive (e.g. only `accept;`) and whether tags/capabilities are used for least privilege;\n\
+         5. Whether the DNS, routes (especially the default route 0.0.0.0/0) and SSO settings are reasonable.\n\
+         List the issues by risk and give fixes that can be applied directly (the matching MCP tool or rule snippet).\n\n",
+    ),
+    ("审计网络 {}", "Audit network {}"),
+    ("请为 ZeroTier 网络 `{}` 编写流规则，实现以下策略：\n\n> {}\n\n", "Please write flow rules for ZeroTier network `{}` that implement the following policy:\n\n> {}\n\n"),
+    (
+        "要求：\n\
+         1. 使用 ZeroTier 规则语言（rules source），在现有规则基础上修改，保留仍然需要的部分；\n\
+         2. 需要区分设备时，定义 tag 并说明应为哪些成员设置什么标签值（参考下方成员列表）；\n\
+         3. 规则末尾要有明确的兜底动作，并确保 ARP、IPv4/IPv6 基础流量不被误拦；\n\
+         4. 输出完整规则源，并逐条解释其作用。\n\n",
+        "Requirements:\n\
+         1. Use the ZeroTier rules language (rules source) and modify the existing rules, keeping the parts that are still needed;\n\
+         2. If devices need to be distinguished, define tags and state which tag values to set on which members (see the member list below);\n\
+         3. End the rules with an explicit fallback action and make sure basic ARP and IPv4/IPv6 traffic is not blocked by mistake;\n\
+         4. Output the complete rules source and explain each rule.\n\n",
+    ),
+    ("为网络 {} 编写流规则", "Write flow rules for network {}"),
+];
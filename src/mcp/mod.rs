@@ -1,5 +1,31 @@
 //! ZeroTier MCP 服务集成
 
 mod server;
+mod access;
+mod audit;
+mod central;
+mod confirm;
+mod controller;
+mod dry_run;
+mod error;
+mod http;
+mod i18n;
+mod output;
+mod policy;
+mod profile;
+mod prompt;
+mod resolve;
+mod resource;
+mod session;
 
+pub use access::AccessPolicy;
+pub use audit::AuditLog;
+pub use confirm::ConfirmationPreview;
+pub use dry_run::DryRunResult;
+pub use http::HttpOptions;
+pub use i18n::Locale;
+pub use policy::{NetworkPolicy, NetworkRule, OperationRule, PolicyError};
+pub use profile::{Profile, ProfileError, ProfileInfo, ProfileList, ProfileSpec, ProfilesConfig};
+pub use resolve::Candidate;
 pub use server::McpServer;
+pub use session::{SessionDefaults, Verbosity};
@@ -0,0 +1,104 @@
+//! 工具结构化输出
+
+use crate::{central, client};
+use rmcp::{
+    handler::server::common::cached_schema_for_type,
+    model::{CallToolResult, Content, JsonObject},
+    schemars::{self, JsonSchema},
+    ErrorData,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// 工具返回值
+pub type ToolResult = Result<CallToolResult, ErrorData>;
+
+/// 结构化成功结果：`structuredContent` 为序列化后的数据，`content` 为简短文本摘要
+pub fn structured<T: Serialize>(value: &T, summary: impl Into<String>) -> ToolResult {
+    let value = serde_json::to_value(value).map_err(|e| {
+        ErrorData::internal_error(format!("failed to serialize structured content: {}", e), None)
+    })?;
+    Ok(CallToolResult {
+        content: vec![Content::text(summary.into())],
+        structured_content: Some(value),
+        is_error: Some(false),
+        meta: None,
+    })
+}
+
+/// 生成工具输出 Schema，用于 `#[tool(output_schema = ...)]`
+pub fn schema<T: JsonSchema + 'static>() -> Arc<JsonObject> {
+    cached_schema_for_type::<T>()
+}
+
+// ============================================
+// 列表与操作结果
+// ============================================
+// MCP 要求输出 Schema 的根为对象，列表统一包装为对象
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NetworkList {
+    pub networks: Vec<client::Network>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PeerList {
+    pub peers: Vec<client::Peer>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CentralNetworkList {
+    pub networks: Vec<central::Network>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CentralMemberList {
+    pub network_id: String,
+    pub members: Vec<central::Member>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ControllerNetworkList {
+    pub networks: Vec<client::ControllerNetwork>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ControllerMemberList {
+    pub network_id: String,
+    pub members: Vec<client::ControllerMember>,
+}
+
+/// 离开/删除网络的结果
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NetworkRef {
+    pub network_id: String,
+}
+
+/// 删除成员的结果
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MemberRef {
+    pub network_id: String,
+    pub member_id: String,
+}
+
+/// Peer 详情：路径附带距上次收发的时长
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PeerDetail {
+    pub address: String,
+    pub version: String,
+    pub role: String,
+    pub latency: i32,
+    pub paths: Vec<PeerPathDetail>,
+}
+
+/// Peer 路径，时长按节点时钟计算
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerPathDetail {
+    #[serde(flatten)]
+    pub path: client::PeerPath,
+    /// 距上次接收的毫秒数，从未接收时为空
+    pub last_receive_age_ms: Option<i64>,
+    /// 距上次发送的毫秒数，从未发送时为空
+    pub last_send_age_ms: Option<i64>,
+}
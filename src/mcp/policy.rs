@@ -0,0 +1,378 @@
+//! 按网络的操作策略
+//!
+//! 用 TOML 文件声明助手可以修改哪些网络、每个网络允许哪些操作。每次写操作前按
+//! 工具参数中的 `network_id` 匹配规则，拒绝时说明是哪条规则阻止了操作。
+//!
+//! ```toml
+//! # 未匹配任何网络规则时使用；省略则默认全部允许
+//! [default]
+//! allow = []
+//!
+//! # 名称以 lab 开头的网络只允许授权/取消授权
+//! [[networks]]
+//! name = "lab*"
+//! allow = ["authorize", "deauthorize"]
+//!
+//! # 生产网络：本机永远不离开
+//! [[networks]]
+//! id = "8056c2e21c000001"
+//! deny = ["leave"]
+//! ```
+
+use super::access::is_mutating;
+use super::error::{ErrorKind, ToolError};
+use super::i18n::tr;
+use super::server::{str_arg, McpServer};
+use rmcp::model::{JsonObject, Tool};
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+
+/// 可在策略中使用的操作名
+pub const OPERATIONS: &[&str] = &[
+    "join",
+    "leave",
+    "update_settings",
+    "authorize",
+    "deauthorize",
+    "update_member",
+    "create_network",
+    "update_network",
+    "delete_network",
+    "delete_member",
+];
+
+/// 按网络的操作策略
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkPolicy {
+    /// 未匹配任何网络规则时使用的规则
+    pub default: Option<OperationRule>,
+    /// 网络规则，按顺序取第一条匹配的
+    #[serde(default)]
+    pub networks: Vec<NetworkRule>,
+}
+
+/// 允许/禁止的操作
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperationRule {
+    /// 允许的操作；省略时允许全部（`deny` 除外）
+    pub allow: Option<Vec<String>>,
+    /// 禁止的操作，优先于 `allow`
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// 单个网络（或一组网络）的规则
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkRule {
+    /// 网络 ID
+    pub id: Option<String>,
+    /// 网络名称，支持以 `*` 结尾的前缀匹配
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub rule: OperationRule,
+}
+
+/// 策略文件错误
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Invalid policy: {0}")]
+    Invalid(String),
+}
+
+impl NetworkPolicy {
+    /// 从 TOML 文本解析
+    pub fn from_toml_str(s: &str) -> Result<Self, PolicyError> {
+        let policy: Self = toml::from_str(s)?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// 读取策略文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    fn validate(&self) -> Result<(), PolicyError> {
+        let rules = self.default.iter().map(|r| ("[default]".to_string(), r));
+        let networks = self.networks.iter().enumerate().map(|(i, n)| (format!("networks[{}]", i), &n.rule));
+        for (label, rule) in rules.chain(networks) {
+            for op in rule.allow.iter().flatten().chain(&rule.deny) {
+                if op != "*" && !OPERATIONS.contains(&op.as_str()) {
+                    return Err(PolicyError::Invalid(format!(
+                        "{}: unknown operation \"{}\" (expected one of: {})",
+                        label,
+                        op,
+                        OPERATIONS.join(", ")
+                    )));
+                }
+            }
+        }
+        for (i, network) in self.networks.iter().enumerate() {
+            if network.id.is_none() && network.name.is_none() {
+                return Err(PolicyError::Invalid(format!("networks[{}]: either `id` or `name` is required", i)));
+            }
+        }
+        Ok(())
+    }
+
+    /// 是否有按名称匹配的规则（需要查询网络名称）
+    fn needs_name(&self) -> bool {
+        self.networks.iter().any(|n| n.id.is_none() && n.name.is_some())
+    }
+
+    /// 检查操作，拒绝时返回原因
+    ///
+    /// `network_id` 为空（如创建网络）时只使用 `[default]` 规则。`network_name` 为空（名称查询失败）时，
+    /// 排在第一条匹配的 ID 规则之前的名称规则无法判断，按拒绝处理，而不是跳过该规则。
+    pub fn check(&self, operation: &str, network_id: Option<&str>, network_name: Option<&str>) -> Result<(), String> {
+        let subject = match (network_id, network_name) {
+            (Some(id), Some(name)) => tr!("网络 {}（{}）的 {} 操作", id, name, operation),
+            (Some(id), None) => tr!("网络 {} 的 {} 操作", id, operation),
+            (None, _) => tr!("{} 操作", operation),
+        };
+
+        let mut matched = None;
+        if let Some(id) = network_id {
+            for (i, network) in self.networks.iter().enumerate() {
+                if network.id.is_none() && network_name.is_none() {
+                    return Err(tr!(
+                        "{}被策略拒绝：规则 networks[{}]（{}）按名称匹配，但无法获取网络名称",
+                        subject,
+                        i,
+                        network
+                    ));
+                }
+                if network.matches(id, network_name) {
+                    matched = Some((i, network));
+                    break;
+                }
+            }
+        }
+
+        let (label, rule) = match matched {
+            Some((i, network)) => (tr!("networks[{}]（{}）", i, network), &network.rule),
+            None => match &self.default {
+                Some(rule) => (tr!("[default] ").to_string(), rule),
+                None => return Ok(()),
+            },
+        };
+
+        if let Some(op) = rule.deny.iter().find(|op| *op == "*" || *op == operation) {
+            return Err(tr!("{}被策略拒绝：规则 {}的 deny 包含 \"{}\"", subject, label, op));
+        }
+        match &rule.allow {
+            Some(allow) if allow.is_empty() => Err(tr!("{}被策略拒绝：规则 {}不允许任何写操作", subject, label)),
+            Some(allow) if !allow.iter().any(|op| op == "*" || op == operation) => Err(tr!(
+                "{}被策略拒绝：规则 {}只允许 {}",
+                subject,
+                label,
+                allow.join(", ")
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl NetworkRule {
+    fn matches(&self, network_id: &str, network_name: Option<&str>) -> bool {
+        if let Some(id) = &self.id {
+            return id.eq_ignore_ascii_case(network_id);
+        }
+        match (&self.name, network_name) {
+            (Some(pattern), Some(name)) => match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => pattern == name,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for NetworkRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.id, &self.name) {
+            (Some(id), _) => write!(f, "id = \"{}\"", id),
+            (None, Some(name)) => write!(f, "name = \"{}\"", name),
+            (None, None) => write!(f, "?"),
+        }
+    }
+}
+
+/// 工具对应的操作名；非写操作返回 `None`，批量操作按 `action` 参数决定
+pub(super) fn operation(tool: &str, arguments: Option<&JsonObject>) -> Option<&'static str> {
+    Some(match tool {
+        "zt_join" => "join",
+        "zt_leave" => "leave",
+        "zt_network_settings" => "update_settings",
+        "zt_central_authorize" | "zt_central_authorize_with_ip" | "zt_controller_authorize" => "authorize",
+        "zt_central_deauthorize" | "zt_controller_deauthorize" => "deauthorize",
+        "zt_central_update_member" | "zt_central_set_member_ips" => "update_member",
+        "zt_central_create_network" | "zt_controller_create_network" => "create_network",
+        "zt_central_update_network" | "zt_central_update_rules" | "zt_controller_update_network" => "update_network",
+        "zt_central_delete_network" | "zt_controller_delete_network" => "delete_network",
+        "zt_central_delete_member" | "zt_controller_delete_member" => "delete_member",
+        "zt_central_bulk_members" => match arguments.and_then(|args| str_arg(args, "action")) {
+            Some("authorize") => "authorize",
+            Some("deauthorize") => "deauthorize",
+            Some("delete") => "delete_member",
+            _ => "update_member",
+        },
+        _ => return None,
+    })
+}
+
+impl McpServer {
+    /// 查询网络名称：控制器工具查本机控制器，其余优先 Central，其次本机已加入的网络
+    async fn network_name(&self, tool: &str, network_id: &str) -> Option<String> {
+        if tool.starts_with("zt_controller_") {
+            let controller = self.local_client.controller();
+            return controller.get_network(network_id).await.ok().map(|n| n.name);
+        }
+        if let Some(central) = &self.central_client {
+            if let Ok(network) = central.networks().get(network_id).await {
+                return network.config.map(|c| c.name);
+            }
+        }
+        self.local_client.networks().get(network_id).await.ok().map(|n| n.name)
+    }
+
+    /// 写操作前按网络策略检查；没有对应操作名的写操作按拒绝处理
+    pub(super) async fn check_network_policy(&self, tool: &Tool, arguments: Option<&JsonObject>) -> Result<(), ToolError> {
+        let Some(policy) = &self.network_policy else {
+            return Ok(());
+        };
+        let mutating = is_mutating(tool);
+        let tool = tool.name.as_ref();
+        let Some(operation) = operation(tool, arguments) else {
+            if !mutating {
+                return Ok(());
+            }
+            return Err(ToolError::new(ErrorKind::Forbidden, tr!("网络策略没有覆盖写操作 {}，按拒绝处理", tool)));
+        };
+        let network_id = arguments.and_then(|args| str_arg(args, "network_id"));
+        // 查询失败时名称为空，由 `check` 对名称规则按拒绝处理
+        let network_name = match network_id {
+            Some(id) if policy.needs_name() => self.network_name(tool, id).await,
+            _ => None,
+        };
+        policy
+            .check(operation, network_id, network_name.as_deref())
+            .map_err(|message| ToolError::new(ErrorKind::Forbidden, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+[default]
+allow = []
+
+[[networks]]
+id = "8056c2e21c000001"
+deny = ["leave"]
+
+[[networks]]
+name = "lab*"
+allow = ["authorize", "deauthorize"]
+
+[[networks]]
+name = "dev"
+"#;
+
+    fn policy() -> NetworkPolicy {
+        NetworkPolicy::from_toml_str(POLICY).unwrap()
+    }
+
+    #[test]
+    fn rule_matches_id_case_insensitively() {
+        let rule = NetworkRule { id: Some("8056C2E21C000001".into()), ..Default::default() };
+        assert!(rule.matches("8056c2e21c000001", None));
+        assert!(!rule.matches("8056c2e21c000002", Some("lab")));
+    }
+
+    #[test]
+    fn rule_matches_name_exactly_or_by_prefix() {
+        let exact = NetworkRule { name: Some("dev".into()), ..Default::default() };
+        assert!(exact.matches("1", Some("dev")));
+        assert!(!exact.matches("1", Some("dev2")));
+        assert!(!exact.matches("1", None));
+
+        let prefix = NetworkRule { name: Some("lab*".into()), ..Default::default() };
+        assert!(prefix.matches("1", Some("lab")));
+        assert!(prefix.matches("1", Some("lab-eu")));
+        assert!(!prefix.matches("1", Some("prod-lab")));
+    }
+
+    #[test]
+    fn id_rule_deny_takes_precedence() {
+        let policy = policy();
+        assert!(policy.check("leave", Some("8056c2e21c000001"), Some("homelab")).is_err());
+        assert!(policy.check("authorize", Some("8056c2e21c000001"), Some("homelab")).is_ok());
+    }
+
+    #[test]
+    fn name_rule_allow_list() {
+        let policy = policy();
+        assert!(policy.check("authorize", Some("aaaaaaaaaaaaaaaa"), Some("lab-eu")).is_ok());
+        assert!(policy.check("delete_network", Some("aaaaaaaaaaaaaaaa"), Some("lab-eu")).is_err());
+        assert!(policy.check("delete_network", Some("aaaaaaaaaaaaaaaa"), Some("dev")).is_ok());
+    }
+
+    #[test]
+    fn unmatched_network_falls_back_to_default() {
+        let policy = policy();
+        assert!(policy.check("authorize", Some("aaaaaaaaaaaaaaaa"), Some("prod")).is_err());
+        assert!(policy.check("create_network", None, None).is_err());
+    }
+
+    #[test]
+    fn no_default_allows_unmatched() {
+        let policy = NetworkPolicy::from_toml_str("[[networks]]\nname = \"lab*\"\nallow = []\n").unwrap();
+        assert!(policy.check("delete_network", Some("aaaaaaaaaaaaaaaa"), Some("prod")).is_ok());
+        assert!(policy.check("delete_network", Some("aaaaaaaaaaaaaaaa"), Some("lab")).is_err());
+    }
+
+    #[test]
+    fn unknown_name_fails_closed() {
+        let policy = NetworkPolicy::from_toml_str("[[networks]]\nname = \"lab*\"\nallow = [\"authorize\"]\n").unwrap();
+        let err = policy.check("delete_network", Some("aaaaaaaaaaaaaaaa"), None).unwrap_err();
+        assert!(err.contains("networks[0]"), "{}", err);
+        assert!(err.contains("lab*"), "{}", err);
+    }
+
+    #[test]
+    fn id_rule_before_name_rules_applies_without_name() {
+        let policy = policy();
+        assert!(policy.check("authorize", Some("8056c2e21c000001"), None).is_ok());
+        assert!(policy.check("authorize", Some("aaaaaaaaaaaaaaaa"), None).is_err());
+    }
+
+    #[test]
+    fn unknown_operation_is_rejected() {
+        assert!(NetworkPolicy::from_toml_str("[default]\ndeny = [\"reboot\"]\n").is_err());
+        assert!(NetworkPolicy::from_toml_str("[[networks]]\nallow = []\n").is_err());
+    }
+
+    #[test]
+    fn every_mutating_tool_maps_to_an_operation() {
+        let server = McpServer::new();
+        for tool in server.tool_router.list_all() {
+            match operation(&tool.name, None) {
+                Some(operation) => assert!(OPERATIONS.contains(&operation), "{} -> {}", tool.name, operation),
+                None => assert!(!is_mutating(&tool), "mutating tool {} has no policy operation", tool.name),
+            }
+        }
+    }
+}
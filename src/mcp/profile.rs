@@ -0,0 +1,264 @@
+//! 多配置（profile）：在一个 MCP 服务中管理多个 Central 账号与本地/远程节点
+//!
+//! 每个工具都接受可选的 `profile` 参数，指定后该次调用使用对应 profile 的客户端；
+//! 未指定时使用默认客户端。资源与提示词始终使用默认客户端。
+//!
+//! ```toml
+//! default = "personal"
+//!
+//! [profiles.personal]
+//! description = "个人账号"
+//! central_token_env = "ZT_PERSONAL_TOKEN"
+//!
+//! [profiles.company]
+//! description = "公司组织"
+//! central_token_env = "ZT_COMPANY_TOKEN"
+//! central_url = "https://api.zerotier.com/api/v1"
+//!
+//! [profiles.nas]
+//! description = "家里的 NAS 节点"
+//! local_url = "http://10.147.20.2:9993"
+//! local_token_env = "ZT_NAS_TOKEN"
+//! ```
+
+use super::error::ToolError;
+use super::i18n::tr;
+use super::server::{str_arg, McpServer};
+use crate::central::{Central, CentralOptions};
+use crate::client::{Client, ClientOptions};
+use rmcp::{
+    model::{JsonObject, Tool},
+    schemars::{self, JsonSchema},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// profile 参数名
+const PROFILE_ARG: &str = "profile";
+
+/// 一组客户端配置
+#[derive(Clone)]
+pub struct Profile {
+    pub(super) local: Client,
+    pub(super) central: Option<Central>,
+    pub(super) description: Option<String>,
+    /// 该 profile 的 Central Token 是否为只读（首次检查后缓存）
+    pub(super) central_read_only: Arc<OnceCell<bool>>,
+}
+
+impl Profile {
+    /// 使用本地客户端创建 profile
+    pub fn new(local: Client) -> Self {
+        Self { local, central: None, description: None, central_read_only: Arc::default() }
+    }
+
+    /// 设置云端客户端
+    pub fn with_central_client(mut self, client: Central) -> Self {
+        self.central = Some(client);
+        self
+    }
+
+    /// 设置说明，显示在 `zt_profiles` 中
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// profile 配置文件
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfilesConfig {
+    /// 未指定 `profile` 参数时使用的 profile
+    pub default: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileSpec>,
+}
+
+/// 单个 profile 的配置；Token 可直接写入，也可从环境变量读取
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileSpec {
+    pub description: Option<String>,
+    /// 本地 Service API 地址，默认 `http://localhost:9993`
+    pub local_url: Option<String>,
+    pub local_token: Option<String>,
+    pub local_token_env: Option<String>,
+    pub local_token_file: Option<String>,
+    pub central_token: Option<String>,
+    pub central_token_env: Option<String>,
+    /// Central API 地址，默认 `https://api.zerotier.com/api/v1`
+    pub central_url: Option<String>,
+}
+
+/// profile 配置错误
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Invalid profile config: {0}")]
+    Invalid(String),
+}
+
+impl ProfilesConfig {
+    /// 从 TOML 文本解析
+    pub fn from_toml_str(s: &str) -> Result<Self, ProfileError> {
+        let config: Self = toml::from_str(s)?;
+        if let Some(default) = &config.default {
+            if !config.profiles.contains_key(default) {
+                return Err(ProfileError::Invalid(format!("default profile \"{}\" is not defined", default)));
+            }
+        }
+        Ok(config)
+    }
+
+    /// 读取配置文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// 创建所有 profile 的客户端
+    pub fn build(&self) -> Result<Vec<(String, Profile)>, ProfileError> {
+        self.profiles
+            .iter()
+            .map(|(name, spec)| Ok((name.clone(), spec.build(name)?)))
+            .collect()
+    }
+}
+
+impl ProfileSpec {
+    /// 创建客户端，`*_env` 指定的环境变量未设置时报错
+    pub fn build(&self, name: &str) -> Result<Profile, ProfileError> {
+        let env = |key: &Option<String>| -> Result<Option<String>, ProfileError> {
+            match key {
+                Some(key) => std::env::var(key).map(Some).map_err(|_| {
+                    ProfileError::Invalid(format!("profile \"{}\": environment variable {} is not set", name, key))
+                }),
+                None => Ok(None),
+            }
+        };
+
+        let local = Client::with_options(ClientOptions {
+            base_url: self.local_url.clone(),
+            token: self.local_token.clone().or(env(&self.local_token_env)?),
+            token_file: self.local_token_file.clone(),
+            ..Default::default()
+        });
+        let mut profile = Profile::new(local);
+        if let Some(token) = self.central_token.clone().or(env(&self.central_token_env)?) {
+            let options = CentralOptions { base_url: self.central_url.clone(), ..Default::default() };
+            profile = profile.with_central_client(Central::with_options(token, options));
+        } else if self.central_url.is_some() {
+            return Err(ProfileError::Invalid(format!(
+                "profile \"{}\": central_url requires central_token or central_token_env",
+                name
+            )));
+        }
+        if let Some(description) = &self.description {
+            profile = profile.with_description(description);
+        }
+        Ok(profile)
+    }
+}
+
+/// profile 信息（不含 Token）
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProfileInfo {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 是否为默认 profile
+    pub default: bool,
+    pub local_url: String,
+    /// 未配置 Central 时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub central_url: Option<String>,
+}
+
+/// `zt_profiles` 输出
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProfileList {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    pub profiles: Vec<ProfileInfo>,
+}
+
+impl McpServer {
+    /// 已配置的 profile 列表
+    pub(super) fn profile_list(&self) -> ProfileList {
+        let profiles = self
+            .profiles
+            .iter()
+            .map(|(name, profile)| ProfileInfo {
+                name: name.clone(),
+                description: profile.description.clone(),
+                default: self.default_profile.as_deref() == Some(name.as_str()),
+                local_url: profile.local.base_url().to_string(),
+                central_url: profile.central.as_ref().map(|c| c.base_url().to_string()),
+            })
+            .collect();
+        ProfileList { default: self.default_profile.clone(), profiles }
+    }
+
+    /// 按参数中的 `profile` 选择客户端；未指定时返回 `None`（使用默认客户端）
+    pub(super) fn resolve_profile(&self, arguments: Option<&JsonObject>) -> Result<Option<Self>, ToolError> {
+        let Some(name) = arguments.and_then(|a| a.get(PROFILE_ARG)) else {
+            return Ok(None);
+        };
+        let Some(name) = name.as_str() else {
+            return Err(ToolError::invalid_params(tr!("profile 必须是字符串")));
+        };
+        self.for_profile(name).map(Some)
+    }
+
+    /// 使用指定 profile 的客户端；profile 不存在时返回的错误中列出可用的 profile
+    pub(super) fn for_profile(&self, name: &str) -> Result<Self, ToolError> {
+        let Some(profile) = self.profiles.get(name) else {
+            let names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            return Err(ToolError::invalid_params(if names.is_empty() {
+                tr!("未知的 profile \"{}\"：未配置任何 profile", name)
+            } else {
+                tr!("未知的 profile \"{}\"，可用: {}", name, names.join(", "))
+            }));
+        };
+        Ok(Self {
+            local_client: self.dry_run_local(profile.local.clone()),
+            central_client: profile.central.clone().map(|c| self.dry_run_central(c)),
+            central_read_only: profile.central_read_only.clone(),
+            ..self.clone()
+        })
+    }
+
+    /// 配置了 profile 时，为每个工具补充可选的 `profile` 参数
+    pub(super) fn profile_schema(&self, mut tool: Tool) -> Tool {
+        if self.profiles.is_empty() {
+            return tool;
+        }
+        let names: Vec<&String> = self.profiles.keys().collect();
+        let description = match self.session.profile().or_else(|| self.default_profile.clone()) {
+            Some(default) => tr!("使用的 profile（账号/节点），省略时使用默认 profile \"{}\"；可用 zt_profiles 查看", default),
+            None => tr!("使用的 profile（账号/节点），省略时使用默认客户端；可用 zt_profiles 查看").to_string(),
+        };
+        let mut input = (*tool.input_schema).clone();
+        let properties = input.entry("properties").or_insert_with(|| Value::Object(JsonObject::new()));
+        if let Value::Object(properties) = properties {
+            properties.insert(PROFILE_ARG.to_string(), json!({ "type": "string", "enum": names, "description": description }));
+        }
+        input.entry("type").or_insert_with(|| json!("object"));
+        tool.input_schema = Arc::new(input);
+        tool
+    }
+}
+
+/// 参数中的 profile 名
+pub(super) fn profile_arg(arguments: &JsonObject) -> Option<&str> {
+    str_arg(arguments, PROFILE_ARG)
+}
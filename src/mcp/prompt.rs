@@ -0,0 +1,204 @@
+//! MCP 提示词：常用 ZeroTier 操作流程，预填实时数据
+
+use super::i18n::tr;
+use super::server::McpServer;
+use rmcp::{
+    handler::server::wrapper::Parameters,
+    model::{GetPromptResult, PromptMessage, PromptMessageRole},
+    prompt, prompt_router,
+    schemars::{self, JsonSchema},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+// ============================================
+// 提示词参数定义
+// ============================================
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct OnboardDeviceArgs {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "新设备的节点 ID（10位十六进制）")]
+    pub member_id: String,
+    #[schemars(description = "设备名称（可选）")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TroubleshootPeerArgs {
+    #[schemars(description = "对端节点 ID（10位十六进制）")]
+    pub peer_id: String,
+    #[schemars(description = "双方所在的网络 ID（可选）")]
+    pub network_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NetworkPromptArgs {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FlowRulesArgs {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "用自然语言描述的访问策略，如「只允许 ops 标签的设备访问 22 端口」")]
+    pub policy: String,
+}
+
+// ============================================
+// 实时数据
+// ============================================
+
+/// 以 JSON 代码块嵌入数据，获取失败时写明原因
+fn snapshot<T: Serialize, E: Display>(title: &str, data: Result<T, E>) -> String {
+    match data {
+        Ok(value) => {
+            let json = serde_json::to_string_pretty(&value).unwrap_or_default();
+            format!("### {}\n\n```json\n{}\n```\n", title, json)
+        }
+        Err(e) => format!("### {}\n\n{}\n", title, tr!("（获取失败: {}）", e)),
+    }
+}
+
+impl McpServer {
+    async fn central_network_snapshot(&self, network_id: &str) -> String {
+        let data = match &self.central_client {
+            Some(c) => c.networks().get(network_id).await.map_err(|e| e.to_string()),
+            None => Err(tr!("未配置 Central API Token（ZEROTIER_CENTRAL_TOKEN）").to_string()),
+        };
+        snapshot(tr!("Central 网络配置"), data)
+    }
+
+    async fn central_members_snapshot(&self, network_id: &str) -> String {
+        let data = match &self.central_client {
+            Some(c) => c.networks().members(network_id).list().await.map_err(|e| e.to_string()),
+            None => Err(tr!("未配置 Central API Token（ZEROTIER_CENTRAL_TOKEN）").to_string()),
+        };
+        snapshot(tr!("Central 网络成员"), data)
+    }
+
+    async fn central_member_snapshot(&self, network_id: &str, member_id: &str) -> String {
+        let data = match &self.central_client {
+            Some(c) => c.networks().members(network_id).get(member_id).await.map_err(|e| e.to_string()),
+            None => Err(tr!("未配置 Central API Token（ZEROTIER_CENTRAL_TOKEN）").to_string()),
+        };
+        snapshot(tr!("Central 成员记录"), data)
+    }
+}
+
+fn user(text: String) -> Vec<PromptMessage> {
+    vec![PromptMessage::new_text(PromptMessageRole::User, text)]
+}
+
+// ============================================
+// 提示词实现
+// ============================================
+
+#[prompt_router(vis = "pub(super)")]
+impl McpServer {
+    /// 新设备入网
+    #[prompt(name = "onboard_device", description = "引导新设备加入网络：核对网络配置、授权成员并分配 IP")]
+    async fn onboard_device(&self, Parameters(args): Parameters<OnboardDeviceArgs>) -> GetPromptResult {
+        let mut text = tr!(
+            "请帮我把节点 `{}` 接入 ZeroTier 网络 `{}`{}。\n\n",
+            args.member_id,
+            args.network_id,
+            args.name.as_deref().map(|n| tr!("，设备名称为「{}」", n)).unwrap_or_default()
+        );
+        text.push_str(tr!(
+            "步骤：\n\
+             1. 根据下方网络配置确认 IP 分配池与路由，选出一个未被占用的地址；\n\
+             2. 若成员记录不存在，提示我在新设备上执行 `zerotier-cli join <网络 ID>`，然后用 `zt_central_members` 确认其出现；\n\
+             3. 用 `zt_central_authorize_with_ip` 授权该成员并设置名称与 IP；\n\
+             4. 再次读取成员记录，确认已授权且 IP 生效。\n\n"
+        ));
+        text.push_str(&self.central_network_snapshot(&args.network_id).await);
+        text.push('\n');
+        text.push_str(&self.central_member_snapshot(&args.network_id, &args.member_id).await);
+        text.push('\n');
+        text.push_str(&self.central_members_snapshot(&args.network_id).await);
+
+        GetPromptResult {
+            description: Some(tr!("将 {} 接入网络 {}", args.member_id, args.network_id)),
+            messages: user(text),
+        }
+    }
+
+    /// 排查节点连通性
+    #[prompt(name = "troubleshoot_peer", description = "排查与某个节点的连通性：检查路径、延迟、授权与网络状态")]
+    async fn troubleshoot_peer(&self, Parameters(args): Parameters<TroubleshootPeerArgs>) -> GetPromptResult {
+        let mut text = tr!("本机无法与节点 `{}` 正常通信，请帮我排查原因。\n\n", args.peer_id);
+        text.push_str(tr!(
+            "请依次检查：\n\
+             1. 本机是否在线、是否处于 TCP 回退（tcpFallbackActive）；\n\
+             2. 对端是否出现在 Peers 中，是否存在活跃且未过期的直连路径，还是只能经由 PLANET/MOON 中继（role / paths）；\n\
+             3. 延迟是否异常（latency 为 -1 表示不可达）；\n\
+             4. 对端在网络中是否已授权、IP 是否已分配、最后在线时间是否过久；\n\
+             5. 本机加入的网络状态是否为 OK。\n\
+             最后给出最可能的原因和具体修复步骤（如防火墙放行 UDP 9993、重新授权等）。\n\n"
+        ));
+        text.push_str(&snapshot(tr!("本机节点状态"), self.local_client.status().await));
+        text.push('\n');
+        text.push_str(&snapshot(tr!("对端 Peer 信息"), self.local_client.peers().get(&args.peer_id).await));
+        if let Some(network_id) = &args.network_id {
+            text.push('\n');
+            text.push_str(&snapshot(tr!("本机网络状态"), self.local_client.networks().get(network_id).await));
+            text.push('\n');
+            text.push_str(&self.central_member_snapshot(network_id, &args.peer_id).await);
+        }
+
+        GetPromptResult {
+            description: Some(tr!("排查与 {} 的连通性", args.peer_id)),
+            messages: user(text),
+        }
+    }
+
+    /// 网络安全审计
+    #[prompt(name = "audit_network", description = "审计网络安全：访问控制、成员授权、桥接与流规则")]
+    async fn audit_network(&self, Parameters(args): Parameters<NetworkPromptArgs>) -> GetPromptResult {
+        let mut text = tr!("请对 ZeroTier 网络 `{}` 做一次安全审计。\n\n", args.network_id);
+        text.push_str(tr!(
+            "重点关注：\n\
+             1. 网络是否为私有（private = false 时任何人都可加入）；\n\
+             2. 已授权但长期未在线、名称为空或来源不明的成员；\n\
+             3. 开启 activeBridge 的成员及其必要性；\n\
+             4. 流规则是否过于宽松（如仅有 `accept;`），是否使用了标签/能力做最小权限；\n\
+             5. DNS、路由（尤其默认路由 0.0.0.0/0）与 SSO 配置是否合理。\n\
+             请按风险高低列出问题，并给出可以直接执行的修复建议（对应的 MCP 工具或规则片段）。\n\n"
+        ));
+        text.push_str(&self.central_network_snapshot(&args.network_id).await);
+        text.push('\n');
+        text.push_str(&self.central_members_snapshot(&args.network_id).await);
+
+        GetPromptResult {
+            description: Some(tr!("审计网络 {}", args.network_id)),
+            messages: user(text),
+        }
+    }
+
+    /// 编写流规则
+    #[prompt(name = "write_flow_rules", description = "根据访问策略为网络编写 ZeroTier 流规则（rules source）")]
+    async fn write_flow_rules(&self, Parameters(args): Parameters<FlowRulesArgs>) -> GetPromptResult {
+        let mut text = tr!(
+            "请为 ZeroTier 网络 `{}` 编写流规则，实现以下策略：\n\n> {}\n\n",
+            args.network_id, args.policy
+        );
+        text.push_str(tr!(
+            "要求：\n\
+             1. 使用 ZeroTier 规则语言（rules source），在现有规则基础上修改，保留仍然需要的部分；\n\
+             2. 需要区分设备时，定义 tag 并说明应为哪些成员设置什么标签值（参考下方成员列表）；\n\
+             3. 规则末尾要有明确的兜底动作，并确保 ARP、IPv4/IPv6 基础流量不被误拦；\n\
+             4. 输出完整规则源，并逐条解释其作用。\n\n"
+        ));
+        text.push_str(&self.central_network_snapshot(&args.network_id).await);
+        text.push('\n');
+        text.push_str(&self.central_members_snapshot(&args.network_id).await);
+
+        GetPromptResult {
+            description: Some(tr!("为网络 {} 编写流规则", args.network_id)),
+            messages: user(text),
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! 名称解析
+//!
+//! Central 工具与本地网络工具的 `network_id` / `member_id` 参数可以填写名称：依次尝试精确匹配、
+//! 忽略大小写匹配与模糊匹配（忽略空格和标点后的包含关系或一两个字符的差异），
+//! 某一层唯一命中即替换为 ID；命中多个时返回候选列表，由调用方选择，不做猜测。
+//! 修改类工具不按模糊匹配执行，模糊命中同样作为候选项返回；解析结果附在工具结果中。
+
+use super::error::{ErrorKind, ToolError};
+use super::i18n::{self, tr};
+use super::server::{str_arg, McpServer};
+use rmcp::model::{CallToolResult, Content, JsonObject, Tool};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// 名称匹配到多个对象时的候选项
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    pub id: String,
+    pub name: String,
+}
+
+/// 已解析为 ID 的名称参数
+#[derive(Debug, Clone, Serialize)]
+pub struct Resolved {
+    /// 参数名（`network_id` 或 `member_id`）
+    pub param: &'static str,
+    pub id: String,
+    pub name: String,
+}
+
+/// 按子串匹配时查询的最少字符数（忽略空格与标点后），过短的查询几乎能匹配任何名称
+const MIN_SUBSTRING_LEN: usize = 3;
+
+/// 名称的查找范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Central 网络与成员
+    Central,
+    /// 本机已加入的网络
+    Local,
+}
+
+fn is_hex_id(value: &str, len: usize) -> bool {
+    value.len() == len && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// 去掉空格与标点并转为小写，用于模糊匹配
+fn normalize(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// 编辑距离（字符级）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb { prev } else { 1 + prev.min(row[j]).min(current) };
+            prev = current;
+        }
+    }
+    row[b.len()]
+}
+
+fn fuzzy_match(query: &str, name: &str) -> bool {
+    let (query, name) = (normalize(query), normalize(name));
+    if query.is_empty() || name.is_empty() {
+        return false;
+    }
+    let (query_len, name_len) = (query.chars().count(), name.chars().count());
+    let tolerance = match query_len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    (query_len >= MIN_SUBSTRING_LEN && name.contains(&query))
+        || (name_len >= MIN_SUBSTRING_LEN && query.contains(&name))
+        || edit_distance(&query, &name) <= tolerance
+}
+
+/// 按精确、忽略大小写、模糊的顺序匹配；`kind` 用于错误信息（“网络”或“成员”）
+///
+/// `exact_only` 为真时（修改类工具）模糊命中即使唯一也作为候选项返回，由调用方确认 ID。
+fn resolve(query: &str, items: Vec<Candidate>, kind: &str, exact_only: bool) -> Result<Candidate, ToolError> {
+    let tiers: [&dyn Fn(&str) -> bool; 3] =
+        [&|name| name == query, &|name| name.eq_ignore_ascii_case(query.trim()), &|name| fuzzy_match(query, name)];
+    for (tier, matches) in tiers.into_iter().enumerate() {
+        let mut found: Vec<Candidate> = items.iter().filter(|c| matches(&c.name)).cloned().collect();
+        let fuzzy = tier == 2;
+        match found.len() {
+            0 => continue,
+            1 if !(fuzzy && exact_only) => return Ok(found.remove(0)),
+            _ => {
+                let message = if fuzzy && exact_only {
+                    tr!("名称 \"{}\" 没有精确匹配的{}，模糊匹配到 {} 个；修改操作不按模糊匹配执行，请从 candidates 中选择 ID 后重试", query, i18n::lookup(kind), found.len())
+                } else {
+                    tr!("名称 \"{}\" 匹配到 {} 个{}，请从 candidates 中选择 ID 后重试", query, found.len(), i18n::lookup(kind))
+                };
+                let mut err = ToolError::new(ErrorKind::Ambiguous, message);
+                for c in &found {
+                    err.message.push_str(&format!("\n  {} {}", c.id, c.name));
+                }
+                err.candidates = found;
+                return Err(err);
+            }
+        }
+    }
+    Err(ToolError::new(ErrorKind::NotFound, tr!("未找到名称为 \"{}\" 的{}", query, i18n::lookup(kind))))
+}
+
+/// 在工具结果中注明名称解析到的 ID：追加一段文本，结构化结果为对象时加入 `resolved` 字段
+pub(super) fn with_resolved(mut result: CallToolResult, resolved: &[Resolved]) -> CallToolResult {
+    if resolved.is_empty() {
+        return result;
+    }
+    let lines: String = resolved.iter().map(|r| format!("\n  {} = {} ({})", r.param, r.id, r.name)).collect();
+    result.content.push(Content::text(tr!("名称已解析为 ID：{}", lines)));
+    if let Some(Value::Object(object)) = &mut result.structured_content {
+        object.entry("resolved").or_insert_with(|| serde_json::to_value(resolved).unwrap_or_default());
+    }
+    result
+}
+
+impl McpServer {
+    /// 工具的名称查找范围；`zt_use_network` 配置了 Central 时查找 Central 网络，否则查找本机网络
+    fn name_scope(&self, tool: &str) -> Option<Scope> {
+        match tool {
+            "zt_leave" | "zt_network" | "zt_network_settings" => Some(Scope::Local),
+            "zt_use_network" if self.central_client.is_some() => Some(Scope::Central),
+            "zt_use_network" => Some(Scope::Local),
+            "zt_central_create_network" => None,
+            _ if tool.starts_with("zt_central_") => Some(Scope::Central),
+            _ => None,
+        }
+    }
+
+    /// 把参数中的网络、成员名称替换为 ID，返回替换过的参数；已是 ID 或工具不支持名称时不做处理
+    ///
+    /// `exact_only` 为真时模糊命中不直接使用，见 [`resolve`]。
+    pub(super) async fn resolve_names(
+        &self,
+        tool: &str,
+        arguments: Option<&mut JsonObject>,
+        exact_only: bool,
+    ) -> Result<Vec<Resolved>, ToolError> {
+        let mut resolved = Vec::new();
+        let (Some(scope), Some(arguments)) = (self.name_scope(tool), arguments) else {
+            return Ok(resolved);
+        };
+
+        if let Some(name) = str_arg(arguments, "network_id").filter(|v| !is_hex_id(v, 16)).map(String::from) {
+            let networks = match scope {
+                Scope::Central => {
+                    let Some(central) = &self.central_client else {
+                        return Err(ToolError::central_not_configured());
+                    };
+                    let networks = central.networks().list().await.map_err(|e| ToolError::central("获取网络失败", &e))?;
+                    networks
+                        .into_iter()
+                        .map(|n| Candidate { name: n.config.map(|c| c.name).unwrap_or_default(), id: n.id })
+                        .collect()
+                }
+                Scope::Local => {
+                    let networks =
+                        self.local_client.networks().list().await.map_err(|e| ToolError::local("获取网络列表失败", &e))?;
+                    networks.into_iter().map(|n| Candidate { id: n.id, name: n.name }).collect()
+                }
+            };
+            let found = resolve(&name, networks, "网络", exact_only)?;
+            arguments.insert("network_id".to_string(), Value::String(found.id.clone()));
+            resolved.push(Resolved { param: "network_id", id: found.id, name: found.name });
+        }
+
+        if scope == Scope::Central {
+            if let Some(name) = str_arg(arguments, "member_id").filter(|v| !is_hex_id(v, 10)).map(String::from) {
+                let Some(central) = &self.central_client else {
+                    return Err(ToolError::central_not_configured());
+                };
+                let network_id = str_arg(arguments, "network_id").unwrap_or_default();
+                let members = central
+                    .networks()
+                    .members(network_id)
+                    .list()
+                    .await
+                    .map_err(|e| ToolError::central("获取成员失败", &e))?;
+                let members = members.into_iter().map(|m| Candidate { id: m.node_id, name: m.name }).collect();
+                let found = resolve(&name, members, "成员", exact_only)?;
+                arguments.insert("member_id".to_string(), Value::String(found.id.clone()));
+                resolved.push(Resolved { param: "member_id", id: found.id, name: found.name });
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// 支持名称的工具在 `network_id` / `member_id` 的说明中注明可填写名称
+    pub(super) fn name_schema(&self, mut tool: Tool) -> Tool {
+        let Some(scope) = self.name_scope(&tool.name) else {
+            return tool;
+        };
+        let mut input = (*tool.input_schema).clone();
+        if let Some(Value::Object(properties)) = input.get_mut("properties") {
+            for (key, hint) in [("network_id", tr!("也可填写网络名称")), ("member_id", tr!("也可填写成员名称"))] {
+                if key == "member_id" && scope == Scope::Local {
+                    continue;
+                }
+                let Some(Value::Object(property)) = properties.get_mut(key) else {
+                    continue;
+                };
+                let description = property.get("description").and_then(Value::as_str).map(i18n::lookup).unwrap_or_default();
+                property.insert("description".to_string(), Value::String(tr!("{}；{}", description, hint)));
+            }
+        }
+        tool.input_schema = Arc::new(input);
+        tool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(names: &[(&str, &str)]) -> Vec<Candidate> {
+        names.iter().map(|&(id, name)| Candidate { id: id.to_string(), name: name.to_string() }).collect()
+    }
+
+    fn members() -> Vec<Candidate> {
+        items(&[
+            ("abcdef0123", "laptop"),
+            ("1111111111", "old-box"),
+            ("2222222222", "Laptop"),
+            ("cccccccc01", "ci-runner-1"),
+            ("cccccccc02", "ci-runner-2"),
+        ])
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("laptop", "laptp"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("网络", "网路"), 1);
+    }
+
+    #[test]
+    fn fuzzy_match_ignores_spacing_and_punctuation() {
+        assert!(fuzzy_match("home lab", "homelab"));
+        assert!(fuzzy_match("HOMELAB", "home-lab"));
+        assert!(fuzzy_match("laptp", "laptop"));
+        assert!(!fuzzy_match("", "laptop"));
+        assert!(!fuzzy_match("--", "laptop"));
+    }
+
+    #[test]
+    fn short_queries_do_not_match_as_substrings() {
+        assert!(!fuzzy_match("a", "laptop"));
+        assert!(!fuzzy_match("la", "laptop"));
+        assert!(fuzzy_match("lap", "laptop"));
+        // 短名称同样不作为查询的子串
+        assert!(!fuzzy_match("laptop", "la"));
+        // 三个字符以内不容许差异
+        assert!(!fuzzy_match("box", "bot"));
+    }
+
+    #[test]
+    fn exact_match_wins_over_case_insensitive() {
+        let found = resolve("laptop", members(), "成员", true).unwrap();
+        assert_eq!(found.id, "abcdef0123");
+        let found = resolve("Laptop", members(), "成员", true).unwrap();
+        assert_eq!(found.id, "2222222222");
+    }
+
+    #[test]
+    fn case_insensitive_tier_reports_every_hit() {
+        let err = resolve("LAPTOP", members(), "成员", false).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Ambiguous);
+        let ids: Vec<&str> = err.candidates.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["abcdef0123", "2222222222"]);
+    }
+
+    #[test]
+    fn case_insensitive_unique_hit_is_used_for_mutating_tools() {
+        let found = resolve("OLD-BOX", members(), "成员", true).unwrap();
+        assert_eq!(found.id, "1111111111");
+    }
+
+    #[test]
+    fn fuzzy_hits_are_candidates_for_mutating_tools() {
+        assert_eq!(resolve("oldbox", members(), "成员", false).unwrap().id, "1111111111");
+        let err = resolve("oldbox", members(), "成员", true).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Ambiguous);
+        assert_eq!(err.candidates.len(), 1);
+        assert_eq!(err.candidates[0].id, "1111111111");
+    }
+
+    #[test]
+    fn fuzzy_tier_with_several_hits_is_ambiguous() {
+        let err = resolve("ci runner", members(), "成员", false).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Ambiguous);
+        assert_eq!(err.candidates.len(), 2);
+    }
+
+    #[test]
+    fn unknown_name_is_not_found() {
+        let err = resolve("desktop", members(), "成员", false).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound);
+        assert!(err.candidates.is_empty());
+        let err = resolve("x", members(), "成员", false).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,300 @@
+//! MCP 资源与订阅
+
+use super::error::{ErrorKind, ToolError};
+use super::i18n;
+use super::server::McpServer;
+use rmcp::{
+    model::{
+        AnnotateAble, RawResource, RawResourceTemplate, ReadResourceResult, Resource, ResourceContents,
+        ResourceTemplate, ResourceUpdatedNotificationParam,
+    },
+    service::Peer,
+    ErrorData, RoleServer,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+const MIME_JSON: &str = "application/json";
+
+/// 默认轮询间隔
+pub(super) const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 可读取的资源
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ResourceUri {
+    LocalStatus,
+    LocalNetworks,
+    LocalNetwork(String),
+    LocalPeers,
+    CentralNetworks,
+    CentralNetwork(String),
+    CentralMembers(String),
+}
+
+impl ResourceUri {
+    fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix("zerotier://")?;
+        let parts: Vec<&str> = path.split('/').collect();
+        match parts.as_slice() {
+            ["local", "status"] => Some(Self::LocalStatus),
+            ["local", "networks"] => Some(Self::LocalNetworks),
+            ["local", "network", id] if !id.is_empty() => Some(Self::LocalNetwork(id.to_string())),
+            ["local", "peers"] => Some(Self::LocalPeers),
+            ["central", "networks"] => Some(Self::CentralNetworks),
+            ["central", "network", id] if !id.is_empty() => Some(Self::CentralNetwork(id.to_string())),
+            ["central", "network", id, "members"] if !id.is_empty() => Some(Self::CentralMembers(id.to_string())),
+            _ => None,
+        }
+    }
+}
+
+fn resource(uri: &str, name: &str, description: &str) -> Resource {
+    RawResource {
+        description: Some(i18n::lookup(description).to_string()),
+        mime_type: Some(MIME_JSON.to_string()),
+        ..RawResource::new(uri, name)
+    }
+    .no_annotation()
+}
+
+fn template(uri_template: &str, name: &str, description: &str) -> ResourceTemplate {
+    RawResourceTemplate {
+        uri_template: uri_template.to_string(),
+        name: name.to_string(),
+        title: None,
+        description: Some(i18n::lookup(description).to_string()),
+        mime_type: Some(MIME_JSON.to_string()),
+    }
+    .no_annotation()
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Result<Value, ToolError> {
+    serde_json::to_value(value).map_err(|e| ToolError::new(ErrorKind::InvalidResponse, e.to_string()))
+}
+
+impl McpServer {
+    /// 固定资源列表
+    pub(super) fn resource_list(&self) -> Vec<Resource> {
+        let mut resources = vec![
+            resource("zerotier://local/status", "local-status", "本地节点状态"),
+            resource("zerotier://local/networks", "local-networks", "已加入的网络"),
+            resource("zerotier://local/peers", "local-peers", "本地节点的 Peers"),
+        ];
+        if self.central_client.is_some() {
+            resources.push(resource("zerotier://central/networks", "central-networks", "云端网络列表"));
+        }
+        resources
+    }
+
+    /// 资源模板列表
+    pub(super) fn resource_templates(&self) -> Vec<ResourceTemplate> {
+        let mut templates = vec![template(
+            "zerotier://local/network/{id}",
+            "local-network",
+            "已加入网络的本地状态",
+        )];
+        if self.central_client.is_some() {
+            templates.push(template("zerotier://central/network/{id}", "central-network", "云端网络配置"));
+            templates.push(template(
+                "zerotier://central/network/{id}/members",
+                "central-members",
+                "云端网络成员列表",
+            ));
+        }
+        templates
+    }
+
+    /// 读取资源内容（JSON）
+    async fn resource_json(&self, uri: &ResourceUri) -> Result<Value, ToolError> {
+        let central = || self.central_client.as_ref().ok_or_else(ToolError::central_not_configured);
+        match uri {
+            ResourceUri::LocalStatus => match self.local_client.status().await {
+                Ok(status) => to_value(&status),
+                Err(e) => Err(ToolError::local("获取状态失败", &e)),
+            },
+            ResourceUri::LocalNetworks => match self.local_client.networks().list().await {
+                Ok(networks) => to_value(&networks),
+                Err(e) => Err(ToolError::local("获取网络失败", &e)),
+            },
+            ResourceUri::LocalNetwork(id) => match self.local_client.networks().get(id).await {
+                Ok(network) => to_value(&network),
+                Err(e) => Err(ToolError::local("获取网络失败", &e)),
+            },
+            ResourceUri::LocalPeers => match self.local_client.peers().list().await {
+                Ok(peers) => to_value(&peers),
+                Err(e) => Err(ToolError::local("获取 Peers 失败", &e)),
+            },
+            ResourceUri::CentralNetworks => match central()?.networks().list().await {
+                Ok(networks) => to_value(&networks),
+                Err(e) => Err(ToolError::central("获取网络失败", &e)),
+            },
+            ResourceUri::CentralNetwork(id) => match central()?.networks().get(id).await {
+                Ok(network) => to_value(&network),
+                Err(e) => Err(ToolError::central("获取网络失败", &e)),
+            },
+            ResourceUri::CentralMembers(id) => match central()?.networks().members(id).list().await {
+                Ok(members) => to_value(&members),
+                Err(e) => Err(ToolError::central("获取成员失败", &e)),
+            },
+        }
+    }
+
+    /// 处理 `resources/read`
+    pub(super) async fn read_resource_uri(&self, uri: &str) -> Result<ReadResourceResult, ErrorData> {
+        let parsed = ResourceUri::parse(uri)
+            .ok_or_else(|| ErrorData::resource_not_found(format!("unknown resource: {}", uri), None))?;
+        let value = self.resource_json(&parsed).await.map_err(|e| {
+            let data = serde_json::to_value(&e).ok();
+            match e.kind {
+                ErrorKind::NotFound => ErrorData::resource_not_found(e.message, data),
+                ErrorKind::CentralNotConfigured | ErrorKind::InvalidParams => ErrorData::invalid_params(e.message, data),
+                _ => ErrorData::internal_error(e.message, data),
+            }
+        })?;
+        let text = serde_json::to_string_pretty(&value).unwrap_or_default();
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(MIME_JSON.to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+
+    /// 处理 `resources/subscribe`：启动轮询任务，内容变化时通知客户端
+    pub(super) fn subscribe_uri(&self, uri: &str, peer: Peer<RoleServer>) -> Result<(), ErrorData> {
+        let parsed = ResourceUri::parse(uri)
+            .ok_or_else(|| ErrorData::resource_not_found(format!("unknown resource: {}", uri), None))?;
+        // 任务不持有本会话的订阅表，会话结束时订阅表随之释放并终止任务
+        let server = McpServer { subscriptions: Subscriptions::default(), ..self.clone() };
+        let uri = uri.to_string();
+        let interval = self.poll_interval;
+
+        let task_uri = uri.clone();
+        let handle = tokio::spawn(async move {
+            let mut last = server.resource_json(&parsed).await.ok().map(fingerprint);
+            loop {
+                tokio::time::sleep(interval).await;
+                if peer.is_transport_closed() {
+                    break;
+                }
+                let current = server.resource_json(&parsed).await.ok().map(fingerprint);
+                if current == last {
+                    continue;
+                }
+                last = current;
+                let param = ResourceUpdatedNotificationParam { uri: task_uri.clone() };
+                if peer.notify_resource_updated(param).await.is_err() {
+                    // 客户端已断开
+                    break;
+                }
+            }
+        });
+        self.subscriptions.insert(uri, handle);
+        Ok(())
+    }
+
+    /// 处理 `resources/unsubscribe`
+    pub(super) fn unsubscribe_uri(&self, uri: &str) {
+        self.subscriptions.remove(uri);
+    }
+}
+
+/// 每次读取都可能变化、不代表状态变更的字段（时钟、延迟、最近收发/在线时间）
+const VOLATILE_FIELDS: &[&str] = &["clock", "latency", "lastReceive", "lastSend", "lastSeen", "lastOnline"];
+
+/// 递归去除易变字段（包括数组中的对象），只有状态真正变化时才通知
+fn fingerprint(mut value: Value) -> Value {
+    strip_volatile(&mut value);
+    value
+}
+
+fn strip_volatile(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !VOLATILE_FIELDS.contains(&key.as_str()));
+            map.values_mut().for_each(strip_volatile);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_volatile),
+        _ => {}
+    }
+}
+
+/// 资源订阅，每个 URI 对应一个轮询任务；最后一个克隆释放（会话结束）时终止全部任务
+#[derive(Clone, Default)]
+pub(super) struct Subscriptions {
+    tasks: Arc<SubscriptionTasks>,
+}
+
+#[derive(Default)]
+struct SubscriptionTasks(Mutex<HashMap<String, JoinHandle<()>>>);
+
+impl Drop for SubscriptionTasks {
+    fn drop(&mut self) {
+        let tasks = self.0.get_mut().unwrap_or_else(|e| e.into_inner());
+        for (_, handle) in tasks.drain() {
+            handle.abort();
+        }
+    }
+}
+
+impl Subscriptions {
+    fn insert(&self, uri: String, handle: JoinHandle<()>) {
+        let mut tasks = self.tasks.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = tasks.insert(uri, handle) {
+            old.abort();
+        }
+    }
+
+    fn remove(&self, uri: &str) {
+        let mut tasks = self.tasks.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(handle) = tasks.remove(uri) {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    /// 任务被终止时丢弃发送端，接收端随之返回错误
+    fn pending_task() -> (JoinHandle<()>, oneshot::Receiver<()>) {
+        let (tx, rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let _tx = tx;
+            std::future::pending::<()>().await;
+        });
+        (handle, rx)
+    }
+
+    #[tokio::test]
+    async fn dropping_the_last_clone_aborts_tasks() {
+        let subscriptions = Subscriptions::default();
+        let (handle, mut rx) = pending_task();
+        subscriptions.insert("zerotier://local/status".to_string(), handle);
+        let clone = subscriptions.clone();
+        drop(subscriptions);
+        tokio::task::yield_now().await;
+        assert!(rx.try_recv().is_err_and(|e| e == oneshot::error::TryRecvError::Empty));
+        drop(clone);
+        assert!(rx.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn resubscribe_and_unsubscribe_abort_the_previous_task() {
+        let subscriptions = Subscriptions::default();
+        let (first, first_rx) = pending_task();
+        let (second, second_rx) = pending_task();
+        subscriptions.insert("zerotier://local/peers".to_string(), first);
+        subscriptions.insert("zerotier://local/peers".to_string(), second);
+        assert!(first_rx.await.is_err());
+        subscriptions.remove("zerotier://local/peers");
+        assert!(second_rx.await.is_err());
+    }
+}
@@ -1,21 +1,71 @@
-use crate::{central::Central, client::Client};
+use super::access::{is_mutating, AccessPolicy};
+use super::audit::{AuditLog, AuditRecord};
+use super::confirm::{Confirmations, DEFAULT_CONFIRMATION_TTL};
+use super::error::{failure, ToolError};
+use super::i18n::{self, tr, Locale};
+use super::policy::NetworkPolicy;
+use super::profile::{Profile, ProfileList};
+use super::output::{
+    schema, structured, CentralMemberList, CentralNetworkList, NetworkList, NetworkRef, PeerDetail, PeerList,
+    PeerPathDetail, ToolResult,
+};
+use super::resolve::with_resolved;
+use super::resource::{Subscriptions, DEFAULT_POLL_INTERVAL};
+use super::session::Session;
+use crate::{
+    central::{Central, Member},
+    client::{Client, Network as LocalNetwork, NetworkSettingsBuilder, NodeStatus, Peer},
+};
 use rmcp::{
-    handler::server::{router::tool::ToolRouter, tool::Parameters},
-    model::{ProtocolVersion, ServerCapabilities, ServerInfo},
+    handler::server::{
+        prompt::PromptContext,
+        router::{prompt::PromptRouter, tool::ToolRouter},
+        tool::ToolCallContext,
+        wrapper::Parameters,
+    },
+    model::{
+        CallToolRequestParam, CallToolResult, JsonObject, GetPromptRequestParam, GetPromptResult, ListPromptsResult, ListResourceTemplatesResult,
+        ListResourcesResult, ListToolsResult, PaginatedRequestParam, ProtocolVersion,
+        ReadResourceRequestParam, ReadResourceResult, ServerCapabilities, ServerInfo, SubscribeRequestParam, Tool,
+        UnsubscribeRequestParam,
+    },
+    service::RequestContext,
     schemars::{self, JsonSchema},
-    tool, tool_handler, tool_router,
+    tool, tool_router,
     transport::stdio,
-    ServerHandler, ServiceExt,
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
 };
 use serde::Deserialize;
-use std::future::Future;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::OnceCell;
 
 /// ZeroTier MCP 服务
 #[derive(Clone)]
 pub struct McpServer {
-    local_client: Client,
-    central_client: Option<Central>,
-    tool_router: ToolRouter<Self>,
+    pub(super) local_client: Client,
+    pub(super) central_client: Option<Central>,
+    pub(super) poll_interval: Duration,
+    pub(super) subscriptions: Subscriptions,
+    pub(super) access: AccessPolicy,
+    pub(super) network_policy: Option<Arc<NetworkPolicy>>,
+    /// 二次确认令牌有效期，`None` 表示不启用
+    pub(super) confirmation_ttl: Option<Duration>,
+    pub(super) confirmations: Confirmations,
+    /// 会话默认值（profile、网络、结果详细程度）
+    pub(super) session: Session,
+    pub(super) audit: Option<Arc<AuditLog>>,
+    pub(super) profiles: Arc<BTreeMap<String, Profile>>,
+    pub(super) default_profile: Option<String>,
+    /// Central Token 是否为只读（首次检查后缓存）
+    pub(super) central_read_only: Arc<OnceCell<bool>>,
+    /// 工具说明与结果文本的语言
+    pub(super) locale: Locale,
+    /// 演练模式：写工具只返回预期变更
+    pub(super) dry_run: bool,
+    pub(super) tool_router: ToolRouter<Self>,
+    pub(super) prompt_router: PromptRouter<Self>,
 }
 
 impl McpServer {
@@ -24,28 +74,195 @@ impl McpServer {
         Self {
             local_client: Client::new(),
             central_client: None,
-            tool_router: Self::tool_router(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            subscriptions: Subscriptions::default(),
+            access: AccessPolicy::default(),
+            network_policy: None,
+            confirmation_ttl: None,
+            confirmations: Confirmations::default(),
+            session: Session::default(),
+            audit: None,
+            profiles: Arc::default(),
+            default_profile: None,
+            central_read_only: Arc::default(),
+            locale: Locale::default(),
+            dry_run: false,
+            tool_router: Self::tool_router()
+                + Self::central_tool_router()
+                + Self::controller_tool_router()
+                + Self::session_tool_router(),
+            prompt_router: Self::prompt_router(),
         }
     }
 
     /// 设置本地客户端
     pub fn with_local_client(mut self, client: Client) -> Self {
-        self.local_client = client;
+        self.local_client = self.dry_run_local(client);
         self
     }
 
     /// 设置云端客户端
     pub fn with_central_client(mut self, client: Central) -> Self {
-        self.central_client = Some(client);
+        self.central_client = Some(self.dry_run_central(client));
+        self.central_read_only = Arc::default();
         self
     }
 
     /// 使用 Token 设置云端客户端
-    pub fn with_central_token(mut self, token: impl Into<String>) -> Self {
-        self.central_client = Some(Central::new(token));
+    pub fn with_central_token(self, token: impl Into<String>) -> Self {
+        self.with_central_client(Central::new(token))
+    }
+
+    /// 设置工具访问策略（只读模式、允许/禁止列表）
+    pub fn with_access_policy(mut self, policy: AccessPolicy) -> Self {
+        self.access = policy;
+        self
+    }
+
+    /// 设置按网络的操作策略，写操作前检查
+    pub fn with_network_policy(mut self, policy: NetworkPolicy) -> Self {
+        self.network_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// 启用破坏性操作的二次确认（离开网络、取消授权、删除），令牌默认 120 秒有效
+    pub fn with_confirmation(mut self, enabled: bool) -> Self {
+        self.confirmation_ttl = enabled.then_some(self.confirmation_ttl.unwrap_or(DEFAULT_CONFIRMATION_TTL));
+        self
+    }
+
+    /// 启用二次确认并设置令牌有效期
+    pub fn with_confirmation_ttl(mut self, ttl: Duration) -> Self {
+        self.confirmation_ttl = Some(ttl);
+        self
+    }
+
+    /// 记录写操作审计日志（JSONL）
+    pub fn with_audit_log(mut self, log: AuditLog) -> Self {
+        self.audit = Some(Arc::new(log));
+        self
+    }
+
+    /// 添加 profile，工具可通过 `profile` 参数选用
+    pub fn with_profile(mut self, name: impl Into<String>, profile: Profile) -> Self {
+        Arc::make_mut(&mut self.profiles).insert(name.into(), profile);
         self
     }
 
+    /// 设置默认 profile：未指定 `profile` 参数的工具调用、资源与提示词使用它的客户端
+    ///
+    /// 须在 [`with_profile`](Self::with_profile) 之后调用，名称不存在时不生效。
+    pub fn with_default_profile(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        if let Some(profile) = self.profiles.get(&name).cloned() {
+            self.local_client = self.dry_run_local(profile.local);
+            self.central_client = profile.central.map(|c| self.dry_run_central(c));
+            self.central_read_only = profile.central_read_only;
+            self.default_profile = Some(name);
+        }
+        self
+    }
+
+    /// 设置工具说明、结果文本与错误信息的语言（默认中文）
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 启用演练模式：写工具照常检查策略，但只读取当前状态并返回预期变更，不发送写请求，
+    /// 也不要求二次确认、不记录审计日志；对所有客户端（含 profile）生效
+    pub fn with_dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self.local_client = self.local_client.clone().with_dry_run(enabled);
+        self.central_client = self.central_client.take().map(|c| c.with_dry_run(enabled));
+        self
+    }
+
+    /// 演练模式下让客户端也进入演练
+    pub(super) fn dry_run_local(&self, client: Client) -> Client {
+        if self.dry_run { client.with_dry_run(true) } else { client }
+    }
+
+    /// 演练模式下让云端客户端也进入演练
+    pub(super) fn dry_run_central(&self, client: Central) -> Central {
+        if self.dry_run { client.with_dry_run(true) } else { client }
+    }
+
+    /// 设置资源订阅的轮询间隔（默认 10 秒）
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// 为新会话复制服务：共享客户端与配置，会话状态（资源订阅、待确认操作、会话默认值）独立
+    pub(super) fn new_session(&self) -> Self {
+        Self {
+            subscriptions: Subscriptions::default(),
+            confirmations: Confirmations::default(),
+            session: Session::default(),
+            ..self.clone()
+        }
+    }
+
+    /// 执行工具调用：名称解析后经过访问策略、网络策略、二次确认与审计，结果中注明解析到的 ID
+    async fn dispatch_tool(
+        &self,
+        mut request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(route) = self.tool_router.map.get(request.name.as_ref()) else {
+            return self.tool_router.call(ToolCallContext::new(self, request, context)).await;
+        };
+        // 名称先解析为 ID，后续的策略检查、确认与审计均基于 ID
+        let resolved = match self.resolve_names(&request.name, request.arguments.as_mut(), is_mutating(&route.attr)).await {
+            Ok(resolved) => resolved,
+            Err(err) => return failure(err),
+        };
+        let result = self.dispatch_resolved(&route.attr, request, context).await?;
+        Ok(with_resolved(result, &resolved))
+    }
+
+    /// 名称解析之后的调用流程（演练模式下跳过二次确认与审计）
+    async fn dispatch_resolved(
+        &self,
+        tool: &Tool,
+        mut request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let audit = (self.audit.is_some() && !self.dry_run && is_mutating(tool))
+            .then(|| AuditRecord::new(&request.name, request.arguments.as_ref(), &context));
+
+        let checked = match self.check_tool(tool).await {
+            Ok(()) => self.check_network_policy(tool, request.arguments.as_ref()).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = checked {
+            if let Some(record) = audit {
+                self.write_audit(&record.denied(serde_json::to_value(&err).unwrap_or_default()));
+            }
+            return failure(err);
+        }
+        // 预览阶段尚未执行，不记录审计
+        if !self.dry_run {
+            if let Some(result) = self.confirm_tool(&mut request).await {
+                return result;
+            }
+        }
+
+        if self.dry_run && is_mutating(tool) {
+            let planner = self.dry_run_planner();
+            let result = planner.tool_router.call(ToolCallContext::new(&planner, request, context)).await?;
+            return planner.dry_run_outcome(result);
+        }
+        match audit {
+            Some(record) => {
+                let call = self.tool_router.call(ToolCallContext::new(self, request, context));
+                self.audited(record, call).await
+            }
+            None => self.tool_router.call(ToolCallContext::new(self, request, context)).await,
+        }
+    }
+
     /// 启动 stdio 服务
     pub async fn serve_stdio(self) -> Result<(), Box<dyn std::error::Error>> {
         let server = self.serve(stdio()).await?;
@@ -60,6 +277,11 @@ impl Default for McpServer {
     }
 }
 
+/// 读取字符串参数
+pub(super) fn str_arg<'a>(arguments: &'a JsonObject, key: &str) -> Option<&'a str> {
+    arguments.get(key).and_then(|v| v.as_str())
+}
+
 // ============================================
 // 工具参数定义
 // ============================================
@@ -90,6 +312,125 @@ pub struct AuthorizeWithIpParam {
     pub name: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NetworkSettingsParam {
+    #[schemars(description = "网络 ID（16位十六进制）")]
+    pub network_id: String,
+    #[schemars(description = "是否接受网络下发的 DNS 配置（可选）")]
+    pub allow_dns: Option<bool>,
+    #[schemars(description = "是否允许网络覆盖系统默认路由（可选）")]
+    pub allow_default: Option<bool>,
+    #[schemars(description = "是否允许受管 IP 与路由覆盖公网地址段（可选）")]
+    pub allow_global: Option<bool>,
+    #[schemars(description = "是否接受网络分配的受管 IP 与路由（可选）")]
+    pub allow_managed: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PeerParam {
+    #[schemars(description = "Peer 地址（10位十六进制节点 ID）")]
+    pub address: String,
+}
+
+// ============================================
+// 结果摘要
+// ============================================
+
+fn on_off(v: bool) -> &'static str {
+    if v { tr!("开") } else { tr!("关") }
+}
+
+/// 本地网络摘要：状态、设备、地址、路由、DNS 与本机设置
+fn local_network_summary(network: &LocalNetwork) -> String {
+    let mut lines = vec![
+        tr!("网络 {} ({})，状态 {}，类型 {}", network.id, network.name, network.status, network.network_type),
+        tr!(
+            "设备 {}，MAC {}，MTU {}，配置版本 {}",
+            network.port_device_name,
+            network.mac,
+            network.mtu,
+            network.netconf_revision
+        ),
+    ];
+    if !network.assigned_addresses.is_empty() {
+        lines.push(tr!("地址: {}", network.assigned_addresses.join(", ")));
+    }
+    if !network.routes.is_empty() {
+        let routes: Vec<String> = network
+            .routes
+            .iter()
+            .map(|r| match &r.via {
+                Some(via) => format!("{} via {}", r.target, via),
+                None => r.target.clone(),
+            })
+            .collect();
+        lines.push(tr!("路由: {}", routes.join(", ")));
+    }
+    if let Some(dns) = network.dns.as_ref().filter(|d| !d.domain.is_empty()) {
+        lines.push(format!("DNS: {} ({})", dns.domain, dns.servers.join(", ")));
+    }
+    lines.push(format!(
+        "allowDNS {}, allowDefault {}, allowGlobal {}, allowManaged {}",
+        on_off(network.allow_dns),
+        on_off(network.allow_default),
+        on_off(network.allow_global),
+        on_off(network.allow_managed)
+    ));
+    lines.join("\n")
+}
+
+/// 时长的可读形式
+pub(super) fn format_age(age_ms: Option<i64>) -> String {
+    match age_ms {
+        None => tr!("从未").to_string(),
+        Some(ms) if ms < 60_000 => tr!("{} 秒前", format!("{:.1}", ms as f64 / 1000.0)),
+        Some(ms) if ms < 3_600_000 => tr!("{} 分钟前", ms / 60_000),
+        Some(ms) => tr!("{} 小时前", ms / 3_600_000),
+    }
+}
+
+/// 按节点时钟计算路径的收发时长
+fn peer_detail(peer: Peer, clock: i64) -> PeerDetail {
+    let age = |at: i64| (at > 0).then(|| (clock - at).max(0));
+    let paths = peer
+        .paths
+        .into_iter()
+        .map(|path| PeerPathDetail {
+            last_receive_age_ms: age(path.last_receive),
+            last_send_age_ms: age(path.last_send),
+            path,
+        })
+        .collect();
+    PeerDetail { address: peer.address, version: peer.version, role: peer.role, latency: peer.latency, paths }
+}
+
+fn peer_summary(peer: &PeerDetail) -> String {
+    let mut lines = vec![tr!("Peer {} ({}, {})，延迟 {} ms", peer.address, peer.role, peer.version, peer.latency)];
+    if peer.paths.is_empty() {
+        lines.push(tr!("无直连路径").to_string());
+    }
+    for p in &peer.paths {
+        let mut flags = Vec::new();
+        if p.path.active {
+            flags.push(tr!("活跃"));
+        }
+        if p.path.preferred {
+            flags.push(tr!("首选"));
+        }
+        if p.path.expired {
+            flags.push(tr!("已过期"));
+        }
+        lines.push(tr!(
+            "{} [{}]，接收 {}，发送 {}",
+            p.path.address,
+            flags.join(", "),
+            format_age(p.last_receive_age_ms),
+            format_age(p.last_send_age_ms)
+        ));
+    }
+    lines.join("\n")
+}
+
 // ============================================
 // 工具实现
 // ============================================
@@ -97,144 +438,244 @@ pub struct AuthorizeWithIpParam {
 #[tool_router]
 impl McpServer {
     /// 获取本地 ZeroTier 节点状态
-    #[tool(description = "获取本地 ZeroTier 节点状态")]
-    async fn zt_status(&self) -> String {
+    #[tool(
+        description = "获取本地 ZeroTier 节点状态",
+        output_schema = schema::<NodeStatus>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_status(&self) -> ToolResult {
         match self.local_client.status().await {
             Ok(status) => {
-                format!(
-                    "节点状态:\n- 地址: {}\n- 版本: {}\n- 在线: {}\n- TCP回退: {}",
-                    status.address, status.version, status.online, status.tcp_fallback_active
-                )
+                let summary = tr!(
+                    "节点 {} ({})，{}",
+                    status.address,
+                    status.version,
+                    if status.online { tr!("在线") } else { tr!("离线") }
+                );
+                structured(&status, summary)
             }
-            Err(e) => format!("获取状态失败: {}", e),
+            Err(e) => failure(ToolError::local("获取状态失败", &e)),
         }
     }
 
+    /// 列出可用的 profile
+    #[tool(
+        description = "列出可用的 profile（Central 账号与本地/远程节点），其他工具可通过 profile 参数选用",
+        output_schema = schema::<ProfileList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_profiles(&self) -> ToolResult {
+        let list = self.profile_list();
+        let summary = if list.profiles.is_empty() {
+            tr!("未配置 profile，所有工具使用默认客户端").to_string()
+        } else {
+            let names: Vec<String> = list
+                .profiles
+                .iter()
+                .map(|p| if p.default { tr!("{}（默认）", p.name) } else { p.name.clone() })
+                .collect();
+            tr!("共 {} 个 profile: {}", names.len(), names.join(", "))
+        };
+        structured(&list, summary)
+    }
+
     /// 列出已加入的 ZeroTier 网络
-    #[tool(description = "列出已加入的 ZeroTier 网络")]
-    async fn zt_networks(&self) -> String {
+    #[tool(
+        description = "列出已加入的 ZeroTier 网络",
+        output_schema = schema::<NetworkList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_networks(&self) -> ToolResult {
         match self.local_client.networks().list().await {
             Ok(networks) => {
-                if networks.is_empty() {
-                    return "暂未加入任何网络".to_string();
-                }
-                let mut result = String::from("已加入的网络:\n");
-                for n in networks {
-                    result.push_str(&format!("\n[{}] {}\n", n.id, n.name));
-                    result.push_str(&format!("  状态: {}\n", n.status));
-                    result.push_str(&format!("  IP: {:?}\n", n.assigned_addresses));
-                }
-                result
+                let summary = tr!("已加入 {} 个网络", networks.len());
+                structured(&NetworkList { networks }, summary)
+            }
+            Err(e) => failure(ToolError::local("获取网络失败", &e)),
+        }
+    }
+
+    /// 获取已加入网络的详情
+    #[tool(
+        description = "获取已加入网络的完整信息：状态、设备名、MTU、配置版本、地址、路由、DNS 与本机设置",
+        output_schema = schema::<LocalNetwork>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_network(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
+        match self.local_client.networks().get(&param.network_id).await {
+            Ok(network) => {
+                let summary = local_network_summary(&network);
+                structured(&network, summary)
+            }
+            Err(e) => failure(ToolError::local("获取网络失败", &e)),
+        }
+    }
+
+    /// 修改本机网络设置
+    #[tool(
+        description = "修改本机对已加入网络的设置：allowDNS、allowDefault、allowGlobal、allowManaged",
+        output_schema = schema::<LocalNetwork>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_network_settings(&self, Parameters(param): Parameters<NetworkSettingsParam>) -> ToolResult {
+        let mut builder = NetworkSettingsBuilder::new();
+        if let Some(v) = param.allow_dns {
+            builder = builder.allow_dns(v);
+        }
+        if let Some(v) = param.allow_default {
+            builder = builder.allow_default(v);
+        }
+        if let Some(v) = param.allow_global {
+            builder = builder.allow_global(v);
+        }
+        if let Some(v) = param.allow_managed {
+            builder = builder.allow_managed(v);
+        }
+        let settings = builder.build();
+        if settings.allow_dns.is_none()
+            && settings.allow_default.is_none()
+            && settings.allow_global.is_none()
+            && settings.allow_managed.is_none()
+        {
+            return failure(ToolError::invalid_params("未指定任何要更新的字段"));
+        }
+
+        match self.local_client.networks().update(&param.network_id, &settings).await {
+            Ok(network) => {
+                let summary = tr!("已更新网络设置:\n{}", local_network_summary(&network));
+                structured(&network, summary)
             }
-            Err(e) => format!("获取网络失败: {}", e),
+            Err(e) => failure(ToolError::local("更新网络设置失败", &e)),
         }
     }
 
     /// 加入 ZeroTier 网络
-    #[tool(description = "加入 ZeroTier 网络")]
-    async fn zt_join(&self, Parameters(param): Parameters<NetworkIdParam>) -> String {
+    #[tool(
+        description = "加入 ZeroTier 网络",
+        output_schema = schema::<LocalNetwork>(),
+        annotations(read_only_hint = false, destructive_hint = false, idempotent_hint = true)
+    )]
+    async fn zt_join(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
         match self.local_client.networks().join(&param.network_id).await {
-            Ok(network) => format!("已加入网络: {} ({})", network.id, network.name),
-            Err(e) => format!("加入网络失败: {}", e),
+            Ok(network) => {
+                let summary = tr!("已加入网络: {} ({})", network.id, network.name);
+                structured(&network, summary)
+            }
+            Err(e) => failure(ToolError::local("加入网络失败", &e)),
         }
     }
 
     /// 离开 ZeroTier 网络
-    #[tool(description = "离开 ZeroTier 网络")]
-    async fn zt_leave(&self, Parameters(param): Parameters<NetworkIdParam>) -> String {
+    #[tool(
+        description = "离开 ZeroTier 网络",
+        output_schema = schema::<NetworkRef>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_leave(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
         match self.local_client.networks().leave(&param.network_id).await {
-            Ok(()) => format!("已离开网络: {}", param.network_id),
-            Err(e) => format!("离开网络失败: {}", e),
+            Ok(()) => {
+                let summary = tr!("已离开网络: {}", param.network_id);
+                structured(&NetworkRef { network_id: param.network_id }, summary)
+            }
+            Err(e) => failure(ToolError::local("离开网络失败", &e)),
         }
     }
 
     /// 列出所有 ZeroTier Peers
-    #[tool(description = "列出所有 ZeroTier Peers")]
-    async fn zt_peers(&self) -> String {
+    #[tool(
+        description = "列出所有 ZeroTier Peers",
+        output_schema = schema::<PeerList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_peers(&self) -> ToolResult {
         match self.local_client.peers().list().await {
             Ok(peers) => {
-                if peers.is_empty() {
-                    return "暂无 Peers".to_string();
-                }
-                let mut result = String::from("Peers:\n");
-                for p in peers {
-                    result.push_str(&format!("\n[{}]\n", p.address));
-                    result.push_str(&format!("  角色: {}\n", p.role));
-                    result.push_str(&format!("  版本: {}\n", p.version));
-                    result.push_str(&format!("  延迟: {}ms\n", p.latency));
-                }
-                result
+                let summary = tr!("共 {} 个 Peers", peers.len());
+                structured(&PeerList { peers }, summary)
             }
-            Err(e) => format!("获取 Peers 失败: {}", e),
+            Err(e) => failure(ToolError::local("获取 Peers 失败", &e)),
         }
     }
 
+    /// 获取单个 Peer 的路径详情
+    #[tool(
+        description = "获取单个 Peer 的详情与各条路径，附带距上次收发的时长",
+        output_schema = schema::<PeerDetail>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_peer(&self, Parameters(param): Parameters<PeerParam>) -> ToolResult {
+        let peer = match self.local_client.peers().get(&param.address).await {
+            Ok(peer) => peer,
+            Err(e) => return failure(ToolError::local("获取 Peer 失败", &e)),
+        };
+        // 以节点时钟为准，远程节点与本机时钟可能不一致
+        let clock = match self.local_client.status().await {
+            Ok(status) => status.clock,
+            Err(_) => SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or_default(),
+        };
+        let detail = peer_detail(peer, clock);
+        let summary = peer_summary(&detail);
+        structured(&detail, summary)
+    }
+
     /// 列出云端 ZeroTier 网络
-    #[tool(description = "列出云端 ZeroTier 网络")]
-    async fn zt_central_networks(&self) -> String {
+    #[tool(
+        description = "列出云端 ZeroTier 网络",
+        output_schema = schema::<CentralNetworkList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_central_networks(&self) -> ToolResult {
         let Some(ref client) = self.central_client else {
-            return "未配置 Central API Token".to_string();
+            return failure(ToolError::central_not_configured());
         };
 
         match client.networks().list().await {
             Ok(networks) => {
-                if networks.is_empty() {
-                    return "暂无网络".to_string();
-                }
-                let mut result = String::from("云端网络:\n");
-                for n in networks {
-                    let name = n
-                        .config
-                        .as_ref()
-                        .map(|c| c.name.as_str())
-                        .unwrap_or("未命名");
-                    result.push_str(&format!("\n[{}] {}\n", n.id, name));
-                    result.push_str(&format!(
-                        "  在线: {} / 授权: {} / 总计: {}\n",
-                        n.online_member_count, n.authorized_member_count, n.total_member_count
-                    ));
-                }
-                result
+                let summary = tr!("云端共 {} 个网络", networks.len());
+                structured(&CentralNetworkList { networks }, summary)
             }
-            Err(e) => format!("获取网络失败: {}", e),
+            Err(e) => failure(ToolError::central("获取网络失败", &e)),
         }
     }
 
     /// 列出网络成员
-    #[tool(description = "列出网络成员")]
-    async fn zt_central_members(&self, Parameters(param): Parameters<NetworkIdParam>) -> String {
+    #[tool(
+        description = "列出网络成员",
+        output_schema = schema::<CentralMemberList>(),
+        annotations(read_only_hint = true)
+    )]
+    async fn zt_central_members(&self, Parameters(param): Parameters<NetworkIdParam>) -> ToolResult {
         let Some(ref client) = self.central_client else {
-            return "未配置 Central API Token".to_string();
+            return failure(ToolError::central_not_configured());
         };
 
         match client.networks().members(&param.network_id).list().await {
             Ok(members) => {
-                if members.is_empty() {
-                    return "暂无成员".to_string();
-                }
-                let mut result = format!("网络 {} 的成员:\n", param.network_id);
-                for m in members {
-                    let status = if m.config.as_ref().map(|c| c.authorized).unwrap_or(false) {
-                        "✅"
-                    } else {
-                        "❌"
-                    };
-                    result.push_str(&format!("\n{} [{}] {}\n", status, m.node_id, m.name));
-                    if let Some(config) = &m.config {
-                        result.push_str(&format!("  IP: {:?}\n", config.ip_assignments));
-                    }
-                }
-                result
+                let authorized = members
+                    .iter()
+                    .filter(|m| m.config.as_ref().map(|c| c.authorized).unwrap_or(false))
+                    .count();
+                let summary = tr!(
+                    "网络 {} 共 {} 个成员，已授权 {} 个",
+                    param.network_id,
+                    members.len(),
+                    authorized
+                );
+                structured(&CentralMemberList { network_id: param.network_id, members }, summary)
             }
-            Err(e) => format!("获取成员失败: {}", e),
+            Err(e) => failure(ToolError::central("获取成员失败", &e)),
         }
     }
 
     /// 授权网络成员
-    #[tool(description = "授权网络成员")]
-    async fn zt_central_authorize(&self, Parameters(param): Parameters<MemberParam>) -> String {
+    #[tool(
+        description = "授权网络成员",
+        output_schema = schema::<Member>(),
+        annotations(read_only_hint = false, destructive_hint = false, idempotent_hint = true)
+    )]
+    async fn zt_central_authorize(&self, Parameters(param): Parameters<MemberParam>) -> ToolResult {
         let Some(ref client) = self.central_client else {
-            return "未配置 Central API Token".to_string();
+            return failure(ToolError::central_not_configured());
         };
 
         match client
@@ -243,21 +684,28 @@ impl McpServer {
             .authorize(&param.member_id)
             .await
         {
-            Ok(member) => format!("已授权成员: {} ({})", member.node_id, member.name),
-            Err(e) => format!("授权失败: {}", e),
+            Ok(member) => {
+                let summary = tr!("已授权成员: {} ({})", member.node_id, member.name);
+                structured(&member, summary)
+            }
+            Err(e) => failure(ToolError::central("授权失败", &e)),
         }
     }
 
     /// 授权网络成员并指定 IP 地址
-    #[tool(description = "授权网络成员并指定自定义 IP 地址")]
+    #[tool(
+        description = "授权网络成员并指定自定义 IP 地址",
+        output_schema = schema::<Member>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
     async fn zt_central_authorize_with_ip(
         &self,
         Parameters(param): Parameters<AuthorizeWithIpParam>,
-    ) -> String {
+    ) -> ToolResult {
         use crate::central::{UpdateMemberConfig, UpdateMemberRequest};
 
         let Some(ref client) = self.central_client else {
-            return "未配置 Central API Token".to_string();
+            return failure(ToolError::central_not_configured());
         };
 
         let req = UpdateMemberRequest {
@@ -282,20 +730,22 @@ impl McpServer {
                     .as_ref()
                     .map(|c| c.ip_assignments.join(", "))
                     .unwrap_or_default();
-                format!(
-                    "已授权成员: {} ({})\nIP: {}",
-                    member.node_id, member.name, ips
-                )
+                let summary = tr!("已授权成员: {} ({})，IP: {}", member.node_id, member.name, ips);
+                structured(&member, summary)
             }
-            Err(e) => format!("授权失败: {}", e),
+            Err(e) => failure(ToolError::central("授权失败", &e)),
         }
     }
 
     /// 取消成员授权
-    #[tool(description = "取消成员授权")]
-    async fn zt_central_deauthorize(&self, Parameters(param): Parameters<MemberParam>) -> String {
+    #[tool(
+        description = "取消成员授权",
+        output_schema = schema::<Member>(),
+        annotations(read_only_hint = false, destructive_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_central_deauthorize(&self, Parameters(param): Parameters<MemberParam>) -> ToolResult {
         let Some(ref client) = self.central_client else {
-            return "未配置 Central API Token".to_string();
+            return failure(ToolError::central_not_configured());
         };
 
         match client
@@ -304,22 +754,128 @@ impl McpServer {
             .deauthorize(&param.member_id)
             .await
         {
-            Ok(member) => format!("已取消授权: {} ({})", member.node_id, member.name),
-            Err(e) => format!("取消授权失败: {}", e),
+            Ok(member) => {
+                let summary = tr!("已取消授权: {} ({})", member.node_id, member.name);
+                structured(&member, summary)
+            }
+            Err(e) => failure(ToolError::central("取消授权失败", &e)),
         }
     }
 }
 
-#[tool_handler]
 impl ServerHandler for McpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2025_03_26,
             instructions: Some(
-                "ZeroTier SDK MCP Server - 管理本地和云端 ZeroTier 网络".to_string(),
+                i18n::translate(self.locale, "ZeroTier SDK MCP Server - 管理本地和云端 ZeroTier 网络").to_string(),
             ),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .enable_prompts()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
             ..Default::default()
         }
     }
+
+    async fn call_tool(
+        &self,
+        mut request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        self.session.fill(&self.tool_router, &mut request);
+        i18n::scope(self.locale, async {
+            let result = match self.resolve_profile(request.arguments.as_ref()) {
+                Ok(Some(scoped)) => scoped.dispatch_tool(request, context).await,
+                Ok(None) => self.dispatch_tool(request, context).await,
+                Err(err) => failure(err),
+            };
+            result.map(|r| self.session.apply_verbosity(r))
+        })
+        .await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        i18n::scope(self.locale, async {
+            let tools = self
+                .visible_tools()
+                .await
+                .into_iter()
+                .map(|t| i18n::translate_tool(self.name_schema(self.session_schema(self.profile_schema(self.dry_run_schema(self.confirmation_schema(t)))))))
+                .collect();
+            Ok(ListToolsResult::with_all_items(tools))
+        })
+        .await
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let context = PromptContext::new(self, request.name, request.arguments, context);
+        i18n::scope(self.locale, self.prompt_router.get_prompt(context)).await
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        let prompts = i18n::scope(self.locale, async {
+            self.prompt_router.list_all().into_iter().map(i18n::translate_prompt).collect()
+        })
+        .await;
+        Ok(ListPromptsResult { prompts, next_cursor: None })
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        let resources = i18n::scope(self.locale, async { self.resource_list() }).await;
+        Ok(ListResourcesResult { resources, next_cursor: None })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        let templates = i18n::scope(self.locale, async { self.resource_templates() }).await;
+        Ok(ListResourceTemplatesResult { resource_templates: templates, next_cursor: None })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        i18n::scope(self.locale, self.read_resource_uri(&request.uri)).await
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.subscribe_uri(&request.uri, context.peer)
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.unsubscribe_uri(&request.uri);
+        Ok(())
+    }
 }
@@ -0,0 +1,244 @@
+//! 会话默认值
+//!
+//! 客户端可通过 `zt_use_network` 为当前会话设置默认 profile、默认网络与输出详细程度：
+//! 之后的工具调用省略 `profile` / `network_id` 时自动填入默认值。
+//! HTTP 模式下每个会话独立，stdio 模式下整个连接为一个会话。
+
+use super::error::{failure, ToolError};
+use super::i18n::{self, tr};
+use super::output::{schema, structured, ToolResult};
+use super::server::McpServer;
+use rmcp::{
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::{CallToolRequestParam, CallToolResult, Content, Tool},
+    schemars::{self, JsonSchema},
+    service::RequestContext,
+    tool, tool_router, RoleServer,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// 设置会话默认值的工具，只填入默认 profile，不填入默认网络
+const USE_NETWORK_TOOL: &str = "zt_use_network";
+
+/// 工具结果文本的详细程度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Verbosity {
+    /// 只保留摘要的第一行
+    Brief,
+    /// 完整摘要（默认）
+    #[default]
+    Normal,
+    /// 摘要之后附加结构化结果的 JSON，供不读取 structuredContent 的客户端使用
+    Full,
+}
+
+impl Verbosity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Brief => "brief",
+            Self::Normal => "normal",
+            Self::Full => "full",
+        }
+    }
+
+    /// 按详细程度改写成功结果的文本，错误结果保持不变
+    fn apply(self, mut result: CallToolResult) -> CallToolResult {
+        if self == Self::Normal || result.is_error == Some(true) {
+            return result;
+        }
+        let Some(text) = result.content.first().and_then(|c| c.as_text()).map(|t| t.text.clone()) else {
+            return result;
+        };
+        let text = match self {
+            Self::Brief => text.lines().next().unwrap_or_default().to_string(),
+            _ => match &result.structured_content {
+                Some(value) => format!("{}\n{}", text, serde_json::to_string_pretty(value).unwrap_or_default()),
+                None => text,
+            },
+        };
+        result.content[0] = Content::text(text);
+        result
+    }
+}
+
+/// 当前会话的默认值
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct SessionDefaults {
+    /// 默认 profile，未设置时使用服务的默认 profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// 默认网络 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<String>,
+    /// 默认网络名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_name: Option<String>,
+    /// 工具结果文本的详细程度
+    pub verbosity: Verbosity,
+}
+
+/// 会话状态（按会话隔离）
+#[derive(Clone, Default)]
+pub(super) struct Session {
+    defaults: Arc<Mutex<SessionDefaults>>,
+}
+
+impl Session {
+    fn get(&self) -> SessionDefaults {
+        self.defaults.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set(&self, defaults: SessionDefaults) {
+        *self.defaults.lock().unwrap_or_else(|e| e.into_inner()) = defaults;
+    }
+
+    /// 默认 profile
+    pub(super) fn profile(&self) -> Option<String> {
+        self.get().profile
+    }
+
+    /// 为省略的 `profile` 与必填的 `network_id` 填入会话默认值
+    pub(super) fn fill(&self, router: &ToolRouter<McpServer>, request: &mut CallToolRequestParam) {
+        let defaults = self.get();
+        let network_id = defaults.network_id.filter(|_| {
+            request.name != USE_NETWORK_TOOL
+                && router.map.get(request.name.as_ref()).is_some_and(|route| requires_network(&route.attr))
+        });
+        if defaults.profile.is_none() && network_id.is_none() {
+            return;
+        }
+        let arguments = request.arguments.get_or_insert_with(Default::default);
+        if let Some(profile) = defaults.profile {
+            arguments.entry("profile").or_insert(Value::String(profile));
+        }
+        if let Some(network_id) = network_id {
+            arguments.entry("network_id").or_insert(Value::String(network_id));
+        }
+    }
+
+    /// 按会话的详细程度改写结果
+    pub(super) fn apply_verbosity(&self, result: CallToolResult) -> CallToolResult {
+        self.get().verbosity.apply(result)
+    }
+}
+
+/// 工具是否必须提供 `network_id`
+fn requires_network(tool: &Tool) -> bool {
+    tool.input_schema
+        .get("required")
+        .and_then(Value::as_array)
+        .is_some_and(|required| required.iter().any(|r| r == "network_id"))
+}
+
+// ============================================
+// 工具参数定义
+// ============================================
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UseNetworkParam {
+    #[schemars(description = "默认网络 ID 或名称，之后的工具可省略 network_id（可选）")]
+    pub network_id: Option<String>,
+    #[schemars(description = "默认 profile，之后的工具可省略 profile（可选）")]
+    pub profile: Option<String>,
+    #[schemars(description = "结果文本的详细程度：brief 只保留第一行，normal 完整摘要，full 附加结构化结果 JSON（可选）")]
+    pub verbosity: Option<Verbosity>,
+    #[schemars(description = "为 true 时清除本会话的所有默认值（可选）")]
+    pub clear: Option<bool>,
+}
+
+fn summary(defaults: &SessionDefaults) -> String {
+    let mut parts = Vec::new();
+    if let Some(profile) = &defaults.profile {
+        parts.push(tr!("默认 profile {}", profile));
+    }
+    match (&defaults.network_id, &defaults.network_name) {
+        (Some(id), Some(name)) if !name.is_empty() => parts.push(tr!("默认网络 {} ({})", id, name)),
+        (Some(id), _) => parts.push(tr!("默认网络 {}", id)),
+        _ => {}
+    }
+    parts.push(tr!("详细程度 {}", defaults.verbosity.as_str()));
+    tr!("当前会话: {}", parts.join(", "))
+}
+
+#[tool_router(router = session_tool_router, vis = "pub(super)")]
+impl McpServer {
+    /// 设置会话默认值
+    #[tool(
+        description = "设置本会话的默认网络、profile 与结果详细程度，之后的工具可省略 network_id 与 profile；不带参数调用时返回当前默认值",
+        output_schema = schema::<SessionDefaults>(),
+        annotations(read_only_hint = true, idempotent_hint = true)
+    )]
+    async fn zt_use_network(
+        &self,
+        Parameters(param): Parameters<UseNetworkParam>,
+        context: RequestContext<RoleServer>,
+    ) -> ToolResult {
+        let previous = self.session.get();
+        let mut defaults = if param.clear == Some(true) { SessionDefaults::default() } else { previous.clone() };
+
+        // 先确认 profile 存在，默认网络也在该 profile 的账号/节点上查找
+        let scoped = match &param.profile {
+            Some(name) => match self.for_profile(name) {
+                Ok(scoped) => Some(scoped),
+                Err(err) => return failure(err),
+            },
+            None => None,
+        };
+        let server = scoped.as_ref().unwrap_or(self);
+
+        if let Some(profile) = param.profile {
+            defaults.profile = Some(profile);
+        }
+        if let Some(verbosity) = param.verbosity {
+            defaults.verbosity = verbosity;
+        }
+        if let Some(network_id) = param.network_id {
+            let name = match &server.central_client {
+                Some(central) => match central.networks().get(&network_id).await {
+                    Ok(network) => network.config.map(|c| c.name),
+                    Err(e) => return failure(ToolError::central("获取网络失败", &e)),
+                },
+                // 本地只知道已加入的网络，未加入的网络也允许设为默认
+                None => server.local_client.networks().get(&network_id).await.ok().map(|n| n.name),
+            };
+            defaults.network_id = Some(network_id);
+            defaults.network_name = name;
+        }
+
+        self.session.set(defaults.clone());
+        // 必填的 network_id 随默认网络变为可选，profile 参数的说明注明默认 profile，变化时通知客户端刷新工具列表
+        if defaults.network_id != previous.network_id || defaults.profile != previous.profile {
+            let _ = context.peer.notify_tool_list_changed().await;
+        }
+        structured(&defaults, summary(&defaults))
+    }
+}
+
+impl McpServer {
+    /// 设置了默认网络时，`network_id` 不再必填，并在说明中注明默认值
+    pub(super) fn session_schema(&self, mut tool: Tool) -> Tool {
+        let Some(network_id) = self.session.get().network_id else {
+            return tool;
+        };
+        if tool.name == USE_NETWORK_TOOL || !requires_network(&tool) {
+            return tool;
+        }
+        let mut input = (*tool.input_schema).clone();
+        if let Some(Value::Array(required)) = input.get_mut("required") {
+            required.retain(|r| r != "network_id");
+            if required.is_empty() {
+                input.remove("required");
+            }
+        }
+        if let Some(Value::Object(property)) = input.get_mut("properties").and_then(|p| p.get_mut("network_id")) {
+            let description = property.get("description").and_then(Value::as_str).map(i18n::lookup).unwrap_or_default();
+            let description = tr!("{}；省略时使用会话默认网络 {}", description, network_id);
+            property.insert("description".to_string(), Value::String(description));
+        }
+        tool.input_schema = Arc::new(input);
+        tool
+    }
+}
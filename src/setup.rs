@@ -0,0 +1,307 @@
+//! 基于 CIDR 的网络快速配置（对应 Central 的 "Easy Setup"）
+//!
+//! 从一个网段推导受管路由与 IP 分配池，可同时生成 Central 与自托管控制器的网络配置。
+//!
+//! ```rust
+//! use zerotier_sdk_rust_mcp::setup::NetworkSetup;
+//!
+//! let setup = NetworkSetup::from_cidr("10.147.20.0/24")
+//!     .unwrap()
+//!     .name("homelab")
+//!     .default_gateway("10.147.20.1");
+//!
+//! let central = setup.central_config().unwrap();
+//! let pools = central.ip_assignment_pools.unwrap();
+//! assert_eq!(pools[0].ip_range_start, "10.147.20.1");
+//! assert_eq!(pools[0].ip_range_end, "10.147.20.254");
+//!
+//! let controller = setup.controller_config().unwrap();
+//! assert_eq!(controller.routes.unwrap().len(), 2);
+//! ```
+
+use crate::{central, client};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// IP 网段（CIDR）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// 网段地址（主机位清零）
+    pub fn network(&self) -> IpAddr {
+        from_bits(self.addr, self.bits() & self.mask())
+    }
+
+    /// 网段内最后一个地址（IPv4 即广播地址）
+    pub fn last(&self) -> IpAddr {
+        from_bits(self.addr, (self.bits() & self.mask()) | (!self.mask() & self.full()))
+    }
+
+    /// 前缀长度
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// 是否为 IPv6 网段
+    pub fn is_ipv6(&self) -> bool {
+        self.addr.is_ipv6()
+    }
+
+    /// 判断地址是否属于该网段
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        ip.is_ipv6() == self.is_ipv6() && (to_bits(ip) & self.mask()) == (self.bits() & self.mask())
+    }
+
+    /// 可分配地址范围：IPv4 排除网段地址与广播地址，IPv6 仅排除网段地址
+    pub fn usable_range(&self) -> Option<(IpAddr, IpAddr)> {
+        let start = (self.bits() & self.mask()).checked_add(1)?;
+        let mut end = (self.bits() & self.mask()) | (!self.mask() & self.full());
+        if !self.is_ipv6() {
+            end = end.checked_sub(1)?;
+        }
+        (start <= end).then(|| (from_bits(self.addr, start), from_bits(self.addr, end)))
+    }
+
+    fn width(&self) -> u8 {
+        if self.is_ipv6() { 128 } else { 32 }
+    }
+
+    fn full(&self) -> u128 {
+        if self.is_ipv6() { u128::MAX } else { u32::MAX as u128 }
+    }
+
+    fn mask(&self) -> u128 {
+        let host_bits = (self.width() - self.prefix) as u32;
+        self.full().checked_shr(host_bits).map(|m| m << host_bits).unwrap_or(0) & self.full()
+    }
+
+    fn bits(&self) -> u128 {
+        to_bits(self.addr)
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = s.trim().split_once('/').ok_or_else(|| Error::InvalidCidr(s.to_string()))?;
+        let addr: IpAddr = addr.parse().map_err(|_| Error::InvalidCidr(s.to_string()))?;
+        let prefix: u8 = prefix.parse().map_err(|_| Error::InvalidCidr(s.to_string()))?;
+        let width = if addr.is_ipv6() { 128 } else { 32 };
+        if prefix > width {
+            return Err(Error::InvalidCidr(s.to_string()));
+        }
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network(), self.prefix)
+    }
+}
+
+fn to_bits(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(v4) => u32::from(v4) as u128,
+        IpAddr::V6(v6) => u128::from(v6),
+    }
+}
+
+fn from_bits(like: IpAddr, bits: u128) -> IpAddr {
+    match like {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(bits as u32)),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+    }
+}
+
+/// 网络快速配置构建器
+#[derive(Debug, Clone)]
+pub struct NetworkSetup {
+    cidr: Cidr,
+    name: Option<String>,
+    private: Option<bool>,
+    enable_broadcast: Option<bool>,
+    mtu: Option<i32>,
+    multicast_limit: Option<i32>,
+    routes: Vec<(String, String)>,
+    rfc4193: bool,
+    n6plane: bool,
+    dns: Option<(String, Vec<String>)>,
+}
+
+impl NetworkSetup {
+    /// 从 CIDR 创建（如 `10.147.20.0/24`）
+    pub fn from_cidr(cidr: &str) -> Result<Self, Error> {
+        let cidr: Cidr = cidr.parse()?;
+        if cidr.usable_range().is_none() {
+            return Err(Error::NoUsableAddresses(cidr.to_string()));
+        }
+        Ok(Self {
+            cidr,
+            name: None,
+            private: None,
+            enable_broadcast: None,
+            mtu: None,
+            multicast_limit: None,
+            routes: Vec::new(),
+            rfc4193: false,
+            n6plane: false,
+            dns: None,
+        })
+    }
+
+    /// 网段
+    pub fn cidr(&self) -> &Cidr {
+        &self.cidr
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn private(mut self, v: bool) -> Self {
+        self.private = Some(v);
+        self
+    }
+
+    pub fn enable_broadcast(mut self, v: bool) -> Self {
+        self.enable_broadcast = Some(v);
+        self
+    }
+
+    pub fn mtu(mut self, v: i32) -> Self {
+        self.mtu = Some(v);
+        self
+    }
+
+    pub fn multicast_limit(mut self, v: i32) -> Self {
+        self.multicast_limit = Some(v);
+        self
+    }
+
+    /// 添加经网关转发的路由（网关须位于本网段内）
+    pub fn gateway_route(mut self, target: impl Into<String>, via: impl Into<String>) -> Self {
+        self.routes.push((target.into(), via.into()));
+        self
+    }
+
+    /// 添加默认路由（`0.0.0.0/0` 或 `::/0`）
+    pub fn default_gateway(self, via: impl Into<String>) -> Self {
+        let target = if self.cidr.is_ipv6() { "::/0" } else { "0.0.0.0/0" };
+        self.gateway_route(target, via)
+    }
+
+    /// 启用 RFC4193 IPv6 地址分配
+    pub fn ipv6_rfc4193(mut self, v: bool) -> Self {
+        self.rfc4193 = v;
+        self
+    }
+
+    /// 启用 6PLANE IPv6 地址分配
+    pub fn ipv6_6plane(mut self, v: bool) -> Self {
+        self.n6plane = v;
+        self
+    }
+
+    pub fn dns(mut self, domain: impl Into<String>, servers: Vec<String>) -> Self {
+        self.dns = Some((domain.into(), servers));
+        self
+    }
+
+    /// 推导出的路由：受管路由在前，网关路由在后
+    fn derived_routes(&self) -> Result<Vec<(String, Option<String>)>, Error> {
+        let mut routes = vec![(self.cidr.to_string(), None)];
+        for (target, via) in &self.routes {
+            let target_cidr: Cidr = target.parse()?;
+            let gateway: IpAddr = via.parse().map_err(|_| Error::InvalidAddress(via.clone()))?;
+            if !self.cidr.contains(gateway) {
+                return Err(Error::GatewayOutOfRange { gateway: via.clone(), cidr: self.cidr.to_string() });
+            }
+            routes.push((target_cidr.to_string(), Some(via.clone())));
+        }
+        Ok(routes)
+    }
+
+    fn derived_pool(&self) -> (String, String) {
+        // from_cidr 已保证存在可用地址
+        let (start, end) = self.cidr.usable_range().expect("usable range");
+        (start.to_string(), end.to_string())
+    }
+
+    /// 生成 Central 网络配置
+    pub fn central_config(&self) -> Result<central::CreateNetworkConfig, Error> {
+        let routes = self.derived_routes()?;
+        let (start, end) = self.derived_pool();
+        let v4 = !self.cidr.is_ipv6();
+
+        let mut builder = central::NetworkConfigBuilder::new()
+            .routes(routes.into_iter().map(|(target, via)| central::Route { target, via }).collect())
+            .ip_assignment_pools(vec![central::IpAssignmentPool { ip_range_start: start, ip_range_end: end }])
+            .v4_assign_mode(central::AssignMode { zt: v4, rfc4193: false, n6plane: false })
+            .v6_assign_mode(central::AssignMode { zt: !v4, rfc4193: self.rfc4193, n6plane: self.n6plane });
+
+        if let Some(name) = &self.name {
+            builder = builder.name(name.clone());
+        }
+        if let Some(v) = self.private {
+            builder = builder.private(v);
+        }
+        if let Some(v) = self.enable_broadcast {
+            builder = builder.enable_broadcast(v);
+        }
+        if let Some(v) = self.mtu {
+            builder = builder.mtu(v);
+        }
+        if let Some(v) = self.multicast_limit {
+            builder = builder.multicast_limit(v);
+        }
+        if let Some((domain, servers)) = &self.dns {
+            builder = builder.dns(central::Dns { domain: domain.clone(), servers: servers.clone() });
+        }
+        Ok(builder.build())
+    }
+
+    /// 生成自托管控制器网络配置
+    pub fn controller_config(&self) -> Result<client::ControllerNetworkConfig, Error> {
+        let routes = self.derived_routes()?;
+        let (start, end) = self.derived_pool();
+        let v4 = !self.cidr.is_ipv6();
+
+        Ok(client::ControllerNetworkConfig {
+            name: self.name.clone(),
+            private: self.private,
+            enable_broadcast: self.enable_broadcast,
+            mtu: self.mtu,
+            multicast_limit: self.multicast_limit,
+            routes: Some(routes.into_iter().map(|(target, via)| client::Route { target, via }).collect()),
+            ip_assignment_pools: Some(vec![client::IpAssignmentPool { ip_range_start: start, ip_range_end: end }]),
+            v4_assign_mode: Some(client::AssignMode { zt: v4, rfc4193: false, n6plane: false }),
+            v6_assign_mode: Some(client::AssignMode { zt: !v4, rfc4193: self.rfc4193, n6plane: self.n6plane }),
+            dns: self.dns.as_ref().map(|(domain, servers)| client::Dns { domain: domain.clone(), servers: servers.clone() }),
+            ..Default::default()
+        })
+    }
+}
+
+/// 错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Invalid CIDR: {0}")]
+    InvalidCidr(String),
+
+    #[error("Invalid IP address: {0}")]
+    InvalidAddress(String),
+
+    #[error("No usable addresses in {0}")]
+    NoUsableAddresses(String),
+
+    #[error("Gateway {gateway} is outside {cidr}")]
+    GatewayOutOfRange { gateway: String, cidr: String },
+}
@@ -0,0 +1,57 @@
+use super::plan::diff_member;
+use super::{Change, Error, Plan};
+use crate::central::Central;
+use serde::Serialize;
+
+/// 执行结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApplyReport {
+    /// 新建网络：`(名称, 网络 ID)`
+    pub created_networks: Vec<(String, String)>,
+    pub updated_networks: usize,
+    pub created_members: usize,
+    pub updated_members: usize,
+    pub deauthorized_members: usize,
+}
+
+impl Plan {
+    /// 按顺序执行计划，遇到错误立即返回
+    pub async fn apply(&self, central: &Central) -> Result<ApplyReport, Error> {
+        let networks = central.networks();
+        let mut report = ApplyReport::default();
+
+        for change in &self.changes {
+            match change {
+                Change::CreateNetwork { name, request, members, tags_by_name, .. } => {
+                    let network = networks.create_with(request).await?;
+                    let mut tags_by_name = tags_by_name.clone();
+                    tags_by_name.extend(network.tags_by_name.clone());
+                    for spec in members {
+                        let change = diff_member(spec, None, &network.id, &tags_by_name)?;
+                        networks.members(&network.id).update(&change.member_id, &change.request).await?;
+                        report.created_members += 1;
+                    }
+                    report.created_networks.push((name.clone(), network.id));
+                }
+                Change::UpdateNetwork { network_id, request, .. } => {
+                    networks.update_with(network_id, request).await?;
+                    report.updated_networks += 1;
+                }
+                Change::UpdateMember { network_id, change } => {
+                    networks.members(network_id).update(&change.member_id, &change.request).await?;
+                    if change.create {
+                        report.created_members += 1;
+                    } else {
+                        report.updated_members += 1;
+                    }
+                }
+                Change::DeauthorizeMember { network_id, member_id, .. } => {
+                    networks.members(network_id).deauthorize(member_id).await?;
+                    report.deauthorized_members += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
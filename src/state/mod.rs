@@ -0,0 +1,262 @@
+//! 声明式期望状态（GitOps）
+//!
+//! 用 TOML/YAML/JSON 文件描述 Central 网络及其成员，`plan` 与线上状态对比生成变更，
+//! `apply` 执行变更。文件中未出现的字段不受管理。
+//!
+//! ```toml
+//! [[networks]]
+//! name = "homelab"
+//! description = "家庭实验网络"
+//! cidr = "10.147.20.0/24"
+//! rules_file = "homelab.ztrules"
+//! dns = { domain = "home.arpa", servers = ["10.147.20.1"] }
+//!
+//! [[networks.members]]
+//! id = "abcdef0123"
+//! name = "laptop"
+//! authorized = true
+//! ip_assignments = ["10.147.20.10"]
+//! tags = { department = 100 }
+//! ```
+
+mod plan;
+mod apply;
+
+pub use plan::{Change, FieldChange, MemberChange, Plan};
+pub use apply::ApplyReport;
+
+use crate::central::{self, AssignMode, Dns, IpAssignmentPool, Route};
+use crate::setup::{self, NetworkSetup};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// 期望状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DesiredState {
+    #[serde(default)]
+    pub networks: Vec<NetworkSpec>,
+}
+
+/// 网络期望状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkSpec {
+    /// 网络 ID；为空时按名称匹配，找不到则创建
+    pub id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub private: Option<bool>,
+    pub enable_broadcast: Option<bool>,
+    pub mtu: Option<i32>,
+    pub multicast_limit: Option<i32>,
+    /// 简写：从 CIDR 推导受管路由与分配池（显式 `routes`/`pools` 优先）
+    pub cidr: Option<String>,
+    pub routes: Option<Vec<Route>>,
+    pub pools: Option<Vec<PoolSpec>>,
+    pub dns: Option<Dns>,
+    pub rules_source: Option<String>,
+    /// 从文件读取规则源（相对于状态文件所在目录）
+    pub rules_file: Option<PathBuf>,
+    /// 为 true 时取消授权文件中未列出的成员
+    #[serde(default)]
+    pub prune_members: bool,
+    #[serde(default)]
+    pub members: Vec<MemberSpec>,
+}
+
+/// IP 分配池
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolSpec {
+    pub start: String,
+    pub end: String,
+}
+
+/// 成员期望状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberSpec {
+    pub id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub authorized: Option<bool>,
+    pub ip_assignments: Option<Vec<String>>,
+    /// 标签：键为标签名或数字 ID，值为标签值
+    pub tags: Option<BTreeMap<String, i32>>,
+}
+
+impl DesiredState {
+    /// 从 TOML 文本解析
+    pub fn from_toml_str(s: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// 从 YAML 文本解析
+    pub fn from_yaml_str(s: &str) -> Result<Self, Error> {
+        Ok(serde_yaml::from_str(s)?)
+    }
+
+    /// 按扩展名读取状态文件（`.toml` / `.yaml` / `.yml` / `.json`）
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+
+        let mut state = match ext.as_str() {
+            "toml" => Self::from_toml_str(&text)?,
+            "yaml" | "yml" => Self::from_yaml_str(&text)?,
+            "json" => serde_json::from_str(&text)?,
+            _ => return Err(Error::UnsupportedFormat(path.display().to_string())),
+        };
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for network in &mut state.networks {
+            if let Some(file) = network.rules_file.take() {
+                network.rules_file = Some(if file.is_relative() { base.join(file) } else { file });
+            }
+        }
+        Ok(state)
+    }
+}
+
+/// 由 `NetworkSpec` 推导出的期望网络配置
+#[derive(Debug, Default)]
+pub(crate) struct DesiredNetwork {
+    pub routes: Option<Vec<Route>>,
+    pub pools: Option<Vec<IpAssignmentPool>>,
+    pub v4_assign_mode: Option<AssignMode>,
+    pub v6_assign_mode: Option<AssignMode>,
+    pub rules_source: Option<String>,
+    /// 规则源中声明的标签（名称 → ID），用于在写入前校验成员标签
+    pub tags_by_name: HashMap<String, i32>,
+}
+
+impl NetworkSpec {
+    pub(crate) fn desired(&self) -> Result<DesiredNetwork, Error> {
+        let mut desired = DesiredNetwork::default();
+
+        if let Some(cidr) = &self.cidr {
+            let config = NetworkSetup::from_cidr(cidr)?.central_config()?;
+            desired.routes = config.routes;
+            desired.pools = config.ip_assignment_pools;
+            desired.v4_assign_mode = config.v4_assign_mode.filter(|m| m.zt);
+            desired.v6_assign_mode = config.v6_assign_mode.filter(|m| m.zt);
+        }
+        if let Some(routes) = &self.routes {
+            desired.routes = Some(routes.clone());
+        }
+        if let Some(pools) = &self.pools {
+            desired.pools = Some(pools.iter().map(|p| IpAssignmentPool {
+                ip_range_start: p.start.clone(),
+                ip_range_end: p.end.clone(),
+            }).collect());
+        }
+        // 显式分配池按地址族启用自动分配，否则池中的地址不会分配给成员
+        if let Some(pools) = &desired.pools {
+            let has_family = |v6: bool| pools.iter().any(|p| p.ip_range_start.parse::<IpAddr>().is_ok_and(|ip| ip.is_ipv6() == v6));
+            let auto = || AssignMode { zt: true, rfc4193: false, n6plane: false };
+            if desired.v4_assign_mode.is_none() && has_family(false) {
+                desired.v4_assign_mode = Some(auto());
+            }
+            if desired.v6_assign_mode.is_none() && has_family(true) {
+                desired.v6_assign_mode = Some(auto());
+            }
+        }
+
+        desired.rules_source = match (&self.rules_source, &self.rules_file) {
+            (Some(_), Some(_)) => {
+                return Err(Error::Invalid(format!("network \"{}\": rules_source and rules_file are mutually exclusive", self.name)));
+            }
+            (Some(source), None) => Some(source.clone()),
+            (None, Some(file)) => Some(std::fs::read_to_string(file)?),
+            (None, None) => None,
+        };
+        if let Some(source) = &desired.rules_source {
+            desired.tags_by_name = declared_tags(source);
+        }
+
+        Ok(desired)
+    }
+}
+
+/// 读取规则源中的标签声明（`tag 名称 id 数字 ...;`），得到标签名到 ID 的映射
+fn declared_tags(source: &str) -> HashMap<String, i32> {
+    let tokens: Vec<&str> = source
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ';').filter(|t| !t.is_empty()))
+        .collect();
+    let mut tags = HashMap::new();
+    for (i, _) in tokens.iter().enumerate().filter(|(_, t)| **t == "tag") {
+        let (Some(name), Some(&"id"), Some(id)) = (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3)) else {
+            continue;
+        };
+        if let Ok(id) = id.parse() {
+            tags.insert(name.to_string(), id);
+        }
+    }
+    tags
+}
+
+impl MemberSpec {
+    /// 将标签名解析为 `(标签 ID, 值)`，按 ID 排序
+    pub(crate) fn resolve_tags(&self, network: &str, tags_by_name: &HashMap<String, i32>) -> Result<Option<Vec<(i32, i32)>>, Error> {
+        let Some(tags) = &self.tags else {
+            return Ok(None);
+        };
+
+        let mut resolved = Vec::with_capacity(tags.len());
+        for (key, value) in tags {
+            let id = match key.parse::<i32>() {
+                Ok(id) => id,
+                Err(_) => *tags_by_name.get(key).ok_or_else(|| Error::UnknownTag {
+                    network: network.to_string(),
+                    tag: key.clone(),
+                })?,
+            };
+            resolved.push((id, *value));
+        }
+        resolved.sort_unstable();
+        Ok(Some(resolved))
+    }
+}
+
+/// 错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Central error: {0}")]
+    Central(#[from] central::Error),
+
+    #[error("Setup error: {0}")]
+    Setup(#[from] setup::Error),
+
+    #[error("Unsupported state file format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Network not found: {0}")]
+    NetworkNotFound(String),
+
+    #[error("Multiple networks named \"{0}\", set `id` explicitly")]
+    AmbiguousNetwork(String),
+
+    #[error("Unknown tag \"{tag}\" on network {network}")]
+    UnknownTag { network: String, tag: String },
+
+    #[error("Invalid state: {0}")]
+    Invalid(String),
+}
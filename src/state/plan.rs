@@ -0,0 +1,488 @@
+use super::{DesiredState, Error, MemberSpec, NetworkSpec};
+use crate::central::{Central, CreateNetworkConfig, Member, Network, UpdateMemberConfig, UpdateMemberRequest, UpdateNetworkRequest};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// 单个字段的变更
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// 成员变更
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberChange {
+    pub member_id: String,
+    pub name: String,
+    /// 成员尚不存在于网络中
+    pub create: bool,
+    pub fields: Vec<FieldChange>,
+    pub request: UpdateMemberRequest,
+}
+
+/// 计划中的一项变更
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Change {
+    /// 创建网络，成员在网络创建后按 `members` 写入
+    CreateNetwork {
+        name: String,
+        fields: Vec<FieldChange>,
+        request: UpdateNetworkRequest,
+        members: Vec<MemberSpec>,
+        /// 规则源声明的标签，网络创建后与 Central 返回的标签合并用于解析成员标签
+        tags_by_name: HashMap<String, i32>,
+    },
+    UpdateNetwork {
+        network_id: String,
+        name: String,
+        fields: Vec<FieldChange>,
+        request: UpdateNetworkRequest,
+    },
+    UpdateMember {
+        network_id: String,
+        change: MemberChange,
+    },
+    DeauthorizeMember {
+        network_id: String,
+        member_id: String,
+        name: String,
+    },
+}
+
+/// 变更计划
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Plan {
+    pub changes: Vec<Change>,
+}
+
+impl Plan {
+    /// 没有任何变更
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl DesiredState {
+    /// 对比线上 Central 状态，生成变更计划
+    pub async fn plan(&self, central: &Central) -> Result<Plan, Error> {
+        let networks = central.networks().list().await?;
+        let mut plan = Plan::default();
+
+        for spec in &self.networks {
+            let desired = spec.desired()?;
+            let live = find_network(&networks, spec)?;
+
+            let Some(live) = live else {
+                // 成员在网络创建后才写入，先按规则源声明的标签校验，避免网络已创建而成员写入失败
+                for member in &spec.members {
+                    member.resolve_tags(&spec.name, &desired.tags_by_name)?;
+                }
+                let (fields, request) = diff_network(spec, &desired, None);
+                plan.changes.push(Change::CreateNetwork {
+                    name: spec.name.clone(),
+                    fields,
+                    request,
+                    members: spec.members.clone(),
+                    tags_by_name: desired.tags_by_name.clone(),
+                });
+                continue;
+            };
+
+            let (fields, request) = diff_network(spec, &desired, Some(live));
+            if !fields.is_empty() {
+                plan.changes.push(Change::UpdateNetwork {
+                    network_id: live.id.clone(),
+                    name: spec.name.clone(),
+                    fields,
+                    request,
+                });
+            }
+
+            let members = central.networks().members(&live.id).list().await?;
+            let by_id: HashMap<&str, &Member> = members.iter().map(|m| (m.node_id.as_str(), m)).collect();
+
+            // 规则源随本次计划更新时，成员可以使用其中新声明的标签
+            let mut tags_by_name = live.tags_by_name.clone();
+            tags_by_name.extend(desired.tags_by_name.clone());
+            for member in &spec.members {
+                let live_member = by_id.get(member.id.as_str()).copied();
+                let change = diff_member(member, live_member, &live.id, &tags_by_name)?;
+                if change.create || !change.fields.is_empty() {
+                    plan.changes.push(Change::UpdateMember { network_id: live.id.clone(), change });
+                }
+            }
+
+            if spec.prune_members {
+                plan.changes.extend(prune(spec, &members, &live.id));
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+/// 取消授权文件中未列出、仍处于授权状态的成员
+fn prune(spec: &NetworkSpec, members: &[Member], network_id: &str) -> Vec<Change> {
+    let listed: HashSet<&str> = spec.members.iter().map(|m| m.id.as_str()).collect();
+    members
+        .iter()
+        .filter(|m| m.config.as_ref().is_some_and(|c| c.authorized) && !listed.contains(m.node_id.as_str()))
+        .map(|m| Change::DeauthorizeMember {
+            network_id: network_id.to_string(),
+            member_id: m.node_id.clone(),
+            name: m.name.clone(),
+        })
+        .collect()
+}
+
+fn find_network<'a>(networks: &'a [Network], spec: &NetworkSpec) -> Result<Option<&'a Network>, Error> {
+    if let Some(id) = &spec.id {
+        return networks
+            .iter()
+            .find(|n| n.id.eq_ignore_ascii_case(id))
+            .map(Some)
+            .ok_or_else(|| Error::NetworkNotFound(id.clone()));
+    }
+
+    let mut matches = networks
+        .iter()
+        .filter(|n| n.config.as_ref().map(|c| c.name == spec.name).unwrap_or(false));
+    let first = matches.next();
+    if matches.next().is_some() {
+        return Err(Error::AmbiguousNetwork(spec.name.clone()));
+    }
+    Ok(first)
+}
+
+/// 若期望值与现值不同，记录变更并返回期望值
+fn diff<T: Serialize + Clone>(fields: &mut Vec<FieldChange>, field: &str, before: Option<T>, after: &Option<T>) -> Option<T> {
+    let after = after.as_ref()?;
+    let before = serde_json::to_value(&before).unwrap_or(Value::Null);
+    let after_value = serde_json::to_value(after).unwrap_or(Value::Null);
+    if before == after_value {
+        return None;
+    }
+    fields.push(FieldChange { field: field.to_string(), before, after: after_value });
+    Some(after.clone())
+}
+
+fn diff_network(spec: &NetworkSpec, desired: &super::DesiredNetwork, live: Option<&Network>) -> (Vec<FieldChange>, UpdateNetworkRequest) {
+    let cfg = live.and_then(|n| n.config.as_ref());
+    let mut fields = Vec::new();
+
+    let config = CreateNetworkConfig {
+        name: diff(&mut fields, "name", cfg.map(|c| c.name.clone()), &Some(spec.name.clone())),
+        private: diff(&mut fields, "private", cfg.map(|c| c.private), &spec.private),
+        enable_broadcast: diff(&mut fields, "enable_broadcast", cfg.map(|c| c.enable_broadcast), &spec.enable_broadcast),
+        mtu: diff(&mut fields, "mtu", cfg.map(|c| c.mtu), &spec.mtu),
+        multicast_limit: diff(&mut fields, "multicast_limit", cfg.map(|c| c.multicast_limit), &spec.multicast_limit),
+        routes: diff(&mut fields, "routes", cfg.map(|c| c.routes.clone()), &desired.routes),
+        ip_assignment_pools: diff(&mut fields, "pools", cfg.map(|c| c.ip_assignment_pools.clone()), &desired.pools),
+        v4_assign_mode: diff(&mut fields, "v4_assign_mode", cfg.and_then(|c| c.v4_assign_mode.clone()), &desired.v4_assign_mode),
+        v6_assign_mode: diff(&mut fields, "v6_assign_mode", cfg.and_then(|c| c.v6_assign_mode.clone()), &desired.v6_assign_mode),
+        dns: diff(&mut fields, "dns", cfg.and_then(|c| c.dns.clone()), &spec.dns),
+    };
+    let has_config = !fields.is_empty();
+
+    let description = diff(&mut fields, "description", live.map(|n| n.description.clone()), &spec.description);
+    let rules_source = diff(
+        &mut fields,
+        "rules_source",
+        live.map(|n| n.rules_source.trim().to_string()),
+        &desired.rules_source.as_ref().map(|s| s.trim().to_string()),
+    );
+
+    let request = UpdateNetworkRequest {
+        config: has_config.then_some(config),
+        description,
+        rules_source,
+    };
+    (fields, request)
+}
+
+pub(crate) fn diff_member(spec: &MemberSpec, live: Option<&Member>, network_id: &str, tags_by_name: &HashMap<String, i32>) -> Result<MemberChange, Error> {
+    let cfg = live.and_then(|m| m.config.as_ref());
+    let mut fields = Vec::new();
+
+    let sorted = |mut v: Vec<String>| {
+        v.sort();
+        v
+    };
+    let tags = spec.resolve_tags(network_id, tags_by_name)?;
+
+    let name = diff(&mut fields, "name", live.map(|m| m.name.clone()), &spec.name);
+    let description = diff(&mut fields, "description", live.map(|m| m.description.clone()), &spec.description);
+    let config = UpdateMemberConfig {
+        authorized: diff(&mut fields, "authorized", cfg.map(|c| c.authorized), &spec.authorized),
+        ip_assignments: diff(
+            &mut fields,
+            "ip_assignments",
+            cfg.map(|c| sorted(c.ip_assignments.clone())),
+            &spec.ip_assignments.clone().map(sorted),
+        ),
+        tags: diff(&mut fields, "tags", cfg.map(|c| { let mut t = c.tags.clone(); t.sort_unstable(); t }), &tags),
+        ..Default::default()
+    };
+    let has_config = config.authorized.is_some() || config.ip_assignments.is_some() || config.tags.is_some();
+
+    Ok(MemberChange {
+        member_id: spec.id.clone(),
+        name: spec.name.clone().or_else(|| live.map(|m| m.name.clone())).unwrap_or_default(),
+        create: live.is_none(),
+        fields,
+        request: UpdateMemberRequest {
+            name,
+            description,
+            config: has_config.then_some(config),
+        },
+    })
+}
+
+fn write_fields(f: &mut fmt::Formatter<'_>, fields: &[FieldChange], create: bool) -> fmt::Result {
+    for c in fields {
+        if create {
+            writeln!(f, "    + {} = {}", c.field, c.after)?;
+        } else {
+            writeln!(f, "    ~ {}: {} -> {}", c.field, c.before, c.after)?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "没有变更，Central 已与期望状态一致。");
+        }
+
+        let (mut create, mut update, mut deauthorize) = (0, 0, 0);
+        for change in &self.changes {
+            match change {
+                Change::CreateNetwork { name, fields, members, .. } => {
+                    create += 1 + members.len();
+                    writeln!(f, "+ 创建网络 \"{}\"", name)?;
+                    write_fields(f, fields, true)?;
+                    for m in members {
+                        writeln!(f, "    + 成员 {} \"{}\"", m.id, m.name.as_deref().unwrap_or(""))?;
+                    }
+                }
+                Change::UpdateNetwork { network_id, name, fields, .. } => {
+                    update += 1;
+                    writeln!(f, "~ 更新网络 {} \"{}\"", network_id, name)?;
+                    write_fields(f, fields, false)?;
+                }
+                Change::UpdateMember { network_id, change } => {
+                    if change.create {
+                        create += 1;
+                        writeln!(f, "+ 创建成员 {}/{} \"{}\"", network_id, change.member_id, change.name)?;
+                    } else {
+                        update += 1;
+                        writeln!(f, "~ 更新成员 {}/{} \"{}\"", network_id, change.member_id, change.name)?;
+                    }
+                    write_fields(f, &change.fields, change.create)?;
+                }
+                Change::DeauthorizeMember { network_id, member_id, name } => {
+                    deauthorize += 1;
+                    writeln!(f, "- 取消授权成员 {}/{} \"{}\"", network_id, member_id, name)?;
+                }
+            }
+        }
+        writeln!(f, "\n计划：创建 {} 项，更新 {} 项，取消授权 {} 项。", create, update, deauthorize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::PoolSpec;
+    use serde_json::json;
+
+    fn live_network(spec: &NetworkSpec) -> Network {
+        let desired = spec.desired().unwrap();
+        serde_json::from_value(json!({
+            "id": "8056c2e21c000001",
+            "clock": 0,
+            "config": {
+                "id": "8056c2e21c000001",
+                "name": spec.name,
+                "private": true,
+                "creationTime": 0,
+                "lastModified": 0,
+                "enableBroadcast": true,
+                "mtu": 2800,
+                "multicastLimit": 32,
+                "routes": desired.routes,
+                "ipAssignmentPools": desired.pools,
+                "v4AssignMode": desired.v4_assign_mode,
+                "v6AssignMode": desired.v6_assign_mode,
+            },
+            "description": spec.description.clone().unwrap_or_default(),
+            "rulesSource": "accept;\n",
+            "onlineMemberCount": 0,
+            "authorizedMemberCount": 0,
+            "totalMemberCount": 0,
+        }))
+        .unwrap()
+    }
+
+    fn live_member(id: &str, authorized: bool, ips: &[&str], tags: &[(i32, i32)]) -> Member {
+        serde_json::from_value(json!({
+            "nodeId": id,
+            "name": "laptop",
+            "config": { "authorized": authorized, "creationTime": 0, "ipAssignments": ips, "tags": tags },
+            "lastOnline": 0,
+            "lastSeen": 0,
+            "protocolVersion": 12,
+            "supportsRulesEngine": true,
+        }))
+        .unwrap()
+    }
+
+    fn homelab() -> NetworkSpec {
+        NetworkSpec {
+            name: "homelab".into(),
+            description: Some("家庭实验网络".into()),
+            cidr: Some("10.147.20.0/24".into()),
+            rules_source: Some("accept;".into()),
+            ..Default::default()
+        }
+    }
+
+    fn fields(changes: &[FieldChange]) -> Vec<&str> {
+        changes.iter().map(|c| c.field.as_str()).collect()
+    }
+
+    #[test]
+    fn matching_network_has_no_changes() {
+        let spec = homelab();
+        let live = live_network(&spec);
+        let (changes, request) = diff_network(&spec, &spec.desired().unwrap(), Some(&live));
+        // 规则源比较时忽略首尾空白
+        assert!(changes.is_empty(), "{:?}", fields(&changes));
+        assert!(request.config.is_none() && request.description.is_none() && request.rules_source.is_none());
+    }
+
+    #[test]
+    fn config_is_sent_only_when_a_config_field_changes() {
+        let spec = NetworkSpec { description: Some("新描述".into()), ..homelab() };
+        let live = live_network(&homelab());
+        let (changes, request) = diff_network(&spec, &spec.desired().unwrap(), Some(&live));
+        assert_eq!(fields(&changes), ["description"]);
+        assert!(request.config.is_none());
+        assert_eq!(request.description.as_deref(), Some("新描述"));
+
+        let spec = NetworkSpec { mtu: Some(1400), ..homelab() };
+        let (changes, request) = diff_network(&spec, &spec.desired().unwrap(), Some(&live));
+        assert_eq!(fields(&changes), ["mtu"]);
+        let config = request.config.unwrap();
+        assert_eq!(config.mtu, Some(1400));
+        // 未变化的字段不写入请求
+        assert!(config.name.is_none() && config.routes.is_none());
+    }
+
+    #[test]
+    fn new_network_lists_every_managed_field() {
+        let spec = homelab();
+        let (changes, request) = diff_network(&spec, &spec.desired().unwrap(), None);
+        assert_eq!(fields(&changes), ["name", "routes", "pools", "v4_assign_mode", "description", "rules_source"]);
+        assert!(changes.iter().all(|c| c.before.is_null()));
+        assert!(request.config.is_some());
+    }
+
+    #[test]
+    fn assign_modes_follow_the_pool_address_families() {
+        let cidr = NetworkSpec { cidr: Some("10.147.20.0/24".into()), ..Default::default() }.desired().unwrap();
+        assert!(cidr.v4_assign_mode.is_some_and(|m| m.zt));
+        assert!(cidr.v6_assign_mode.is_none());
+
+        let pools = NetworkSpec {
+            pools: Some(vec![PoolSpec { start: "fd00::1".into(), end: "fd00::ff".into() }]),
+            ..Default::default()
+        }
+        .desired()
+        .unwrap();
+        assert!(pools.v4_assign_mode.is_none());
+        assert!(pools.v6_assign_mode.is_some_and(|m| m.zt && !m.rfc4193 && !m.n6plane));
+
+        let unmanaged = NetworkSpec::default().desired().unwrap();
+        assert!(unmanaged.v4_assign_mode.is_none() && unmanaged.v6_assign_mode.is_none());
+    }
+
+    #[test]
+    fn member_ips_and_tags_compare_regardless_of_order() {
+        let spec = MemberSpec {
+            id: "abcdef0123".into(),
+            ip_assignments: Some(vec!["10.147.20.11".into(), "10.147.20.10".into()]),
+            tags: Some([("department".to_string(), 100), ("2".to_string(), 7)].into()),
+            ..Default::default()
+        };
+        let tags_by_name = HashMap::from([("department".to_string(), 1)]);
+        let live = live_member("abcdef0123", true, &["10.147.20.10", "10.147.20.11"], &[(2, 7), (1, 100)]);
+        let change = diff_member(&spec, Some(&live), "homelab", &tags_by_name).unwrap();
+        assert!(!change.create);
+        assert!(change.fields.is_empty(), "{:?}", fields(&change.fields));
+        assert!(change.request.config.is_none());
+    }
+
+    #[test]
+    fn member_changes_only_send_changed_fields() {
+        let spec = MemberSpec { id: "abcdef0123".into(), authorized: Some(false), name: Some("laptop".into()), ..Default::default() };
+        let live = live_member("abcdef0123", true, &[], &[]);
+        let change = diff_member(&spec, Some(&live), "homelab", &HashMap::new()).unwrap();
+        assert_eq!(fields(&change.fields), ["authorized"]);
+        assert!(change.request.name.is_none());
+        let config = change.request.config.unwrap();
+        assert_eq!(config.authorized, Some(false));
+        assert!(config.ip_assignments.is_none() && config.tags.is_none());
+    }
+
+    #[test]
+    fn missing_member_is_created() {
+        let spec = MemberSpec { id: "abcdef0123".into(), authorized: Some(true), ..Default::default() };
+        let change = diff_member(&spec, None, "homelab", &HashMap::new()).unwrap();
+        assert!(change.create);
+        assert_eq!(fields(&change.fields), ["authorized"]);
+    }
+
+    #[test]
+    fn unknown_tag_name_is_an_error() {
+        let spec = MemberSpec { id: "abcdef0123".into(), tags: Some([("ops".to_string(), 1)].into()), ..Default::default() };
+        assert!(matches!(diff_member(&spec, None, "homelab", &HashMap::new()), Err(Error::UnknownTag { tag, .. }) if tag == "ops"));
+    }
+
+    #[test]
+    fn prune_deauthorizes_only_unlisted_authorized_members() {
+        let spec = NetworkSpec {
+            prune_members: true,
+            members: vec![MemberSpec { id: "abcdef0123".into(), ..Default::default() }],
+            ..homelab()
+        };
+        let members = [
+            live_member("abcdef0123", true, &[], &[]),
+            live_member("1111111111", true, &[], &[]),
+            live_member("2222222222", false, &[], &[]),
+        ];
+        let changes = prune(&spec, &members, "8056c2e21c000001");
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0], Change::DeauthorizeMember { member_id, .. } if member_id == "1111111111"));
+    }
+
+    #[test]
+    fn plan_summary_counts_each_kind_of_change() {
+        assert_eq!(Plan::default().to_string(), "没有变更，Central 已与期望状态一致。\n");
+        let plan = Plan {
+            changes: vec![Change::DeauthorizeMember {
+                network_id: "8056c2e21c000001".into(),
+                member_id: "1111111111".into(),
+                name: "old-box".into(),
+            }],
+        };
+        assert!(plan.to_string().ends_with("计划：创建 0 项，更新 0 项，取消授权 1 项。\n"));
+    }
+}